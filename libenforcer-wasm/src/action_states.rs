@@ -0,0 +1,190 @@
+/// Action state IDs shared by every character, named as in the game's own tables
+pub const DEAD_DOWN: u16 = 0x00;
+pub const SLEEP: u16 = 0x0B; // out of play (eliminated, or waiting to enter)
+pub const REBIRTH: u16 = 0x0C; // respawn platform descending
+pub const REBIRTH_WAIT: u16 = 0x0D; // waiting on the respawn platform
+pub const WAIT: u16 = 0x0E;
+pub const TURN: u16 = 0x12;
+pub const DASH: u16 = 0x14;
+pub const KNEE_BEND: u16 = 0x18; // jumpsquat
+pub const SQUAT: u16 = 0x27;
+pub const SQUAT_WAIT: u16 = 0x28;
+pub const SQUAT_RV: u16 = 0x29;
+pub const ATTACK_11: u16 = 0x2C; // first jab
+pub const ATTACK_HI3: u16 = 0x38; // uptilt
+pub const ATTACK_HI4: u16 = 0x3F; // upsmash
+pub const ATTACK_AIR_LW: u16 = 0x45; // dair
+pub const DAMAGE_HI1: u16 = 0x4B;
+pub const DAMAGE_FLY_ROLL: u16 = 0x5B;
+pub const GUARD_SET_OFF: u16 = 0xB5; // shield stun
+pub const DOWN_ATTACK_U: u16 = 0xBB;
+pub const DOWN_ATTACK_D: u16 = 0xC3;
+pub const CATCH_ATTACK: u16 = 0xD9; // pummel
+pub const CLIFF_ATTACK_SLOW: u16 = 0x100;
+pub const CLIFF_ATTACK_QUICK: u16 = 0x101;
+
+/// Action states from here on mean something different for each character
+pub const FIRST_CHARACTER_STATE: u16 = 0x155;
+
+/// Character IDs as they appear in the game start block
+const FOX: u8 = 2;
+const FALCO: u8 = 20;
+
+/// Common action states 0x00..=0x109 by ID
+const COMMON_STATES: [&str; 266] = [
+    "DeadDown", "DeadLeft", "DeadRight", "DeadUp", "DeadUpStar", "DeadUpStarIce",
+    "DeadUpFall", "DeadUpFallHitCamera", "DeadUpFallHitCameraFlat", "DeadUpFallIce",
+    "DeadUpFallHitCameraIce", "Sleep", "Rebirth", "RebirthWait", "Wait", "WalkSlow",
+    "WalkMiddle", "WalkFast", "Turn", "TurnRun", "Dash", "Run", "RunDirect", "RunBrake",
+    "KneeBend", "JumpF", "JumpB", "JumpAerialF", "JumpAerialB", "Fall", "FallF", "FallB",
+    "FallAerial", "FallAerialF", "FallAerialB", "FallSpecial", "FallSpecialF",
+    "FallSpecialB", "DamageFall", "Squat", "SquatWait", "SquatRv", "Landing",
+    "LandingFallSpecial", "Attack11", "Attack12", "Attack13", "Attack100Start",
+    "Attack100Loop", "Attack100End", "AttackDash", "AttackS3Hi", "AttackS3HiS",
+    "AttackS3S", "AttackS3LwS", "AttackS3Lw", "AttackHi3", "AttackLw3", "AttackS4Hi",
+    "AttackS4HiS", "AttackS4S", "AttackS4LwS", "AttackS4Lw", "AttackHi4", "AttackLw4",
+    "AttackAirN", "AttackAirF", "AttackAirB", "AttackAirHi", "AttackAirLw", "LandingAirN",
+    "LandingAirF", "LandingAirB", "LandingAirHi", "LandingAirLw", "DamageHi1",
+    "DamageHi2", "DamageHi3", "DamageN1", "DamageN2", "DamageN3", "DamageLw1",
+    "DamageLw2", "DamageLw3", "DamageAir1", "DamageAir2", "DamageAir3", "DamageFlyHi",
+    "DamageFlyN", "DamageFlyLw", "DamageFlyTop", "DamageFlyRoll", "LightGet", "HeavyGet",
+    "LightThrowF", "LightThrowB", "LightThrowHi", "LightThrowLw", "LightThrowDash",
+    "LightThrowDrop", "LightThrowAirF", "LightThrowAirB", "LightThrowAirHi",
+    "LightThrowAirLw", "HeavyThrowF", "HeavyThrowB", "HeavyThrowHi", "HeavyThrowLw",
+    "LightThrowF4", "LightThrowB4", "LightThrowHi4", "LightThrowLw4", "LightThrowAirF4",
+    "LightThrowAirB4", "LightThrowAirHi4", "LightThrowAirLw4", "HeavyThrowF4",
+    "HeavyThrowB4", "HeavyThrowHi4", "HeavyThrowLw4", "SwordSwing1", "SwordSwing3",
+    "SwordSwing4", "SwordSwingDash", "BatSwing1", "BatSwing3", "BatSwing4",
+    "BatSwingDash", "ParasolSwing1", "ParasolSwing3", "ParasolSwing4",
+    "ParasolSwingDash", "HarisenSwing1", "HarisenSwing3", "HarisenSwing4",
+    "HarisenSwingDash", "StarRodSwing1", "StarRodSwing3", "StarRodSwing4",
+    "StarRodSwingDash", "LipStickSwing1", "LipStickSwing3", "LipStickSwing4",
+    "LipStickSwingDash", "ItemParasolOpen", "ItemParasolFall", "ItemParasolFallSpecial",
+    "ItemParasolDamageFall", "LGunShoot", "LGunShootAir", "LGunShootEmpty",
+    "LGunShootAirEmpty", "FireFlowerShoot", "FireFlowerShootAir", "ItemScrew",
+    "ItemScrewAir", "DamageScrew", "DamageScrewAir", "ItemScopeStart", "ItemScopeRapid",
+    "ItemScopeFire", "ItemScopeEnd", "ItemScopeAirStart", "ItemScopeAirRapid",
+    "ItemScopeAirFire", "ItemScopeAirEnd", "ItemScopeStartEmpty", "ItemScopeRapidEmpty",
+    "ItemScopeFireEmpty", "ItemScopeEndEmpty", "ItemScopeAirStartEmpty",
+    "ItemScopeAirRapidEmpty", "ItemScopeAirFireEmpty", "ItemScopeAirEndEmpty",
+    "LiftWait", "LiftWalk1", "LiftWalk2", "LiftTurn", "GuardOn", "Guard", "GuardOff",
+    "GuardSetOff", "GuardReflect", "DownBoundU", "DownWaitU", "DownDamageU",
+    "DownStandU", "DownAttackU", "DownFowardU", "DownBackU", "DownSpotU", "DownBoundD",
+    "DownWaitD", "DownDamageD", "DownStandD", "DownAttackD", "DownFowardD", "DownBackD",
+    "DownSpotD", "Passive", "PassiveStandF", "PassiveStandB", "PassiveWall",
+    "PassiveWallJump", "PassiveCeil", "ShieldBreakFly", "ShieldBreakFall",
+    "ShieldBreakDownU", "ShieldBreakDownD", "ShieldBreakStandU", "ShieldBreakStandD",
+    "FuraFura", "Catch", "CatchPull", "CatchDash", "CatchDashPull", "CatchWait",
+    "CatchAttack", "CatchCut", "ThrowF", "ThrowB", "ThrowHi", "ThrowLw",
+    "CapturePulledHi", "CaptureWaitHi", "CaptureDamageHi", "CapturePulledLw",
+    "CaptureWaitLw", "CaptureDamageLw", "CaptureCut", "CaptureJump", "CaptureNeck",
+    "CaptureFoot", "EscapeF", "EscapeB", "Escape", "EscapeAir", "ReboundStop", "Rebound",
+    "ThrownF", "ThrownB", "ThrownHi", "ThrownLw", "ThrownLwWomen", "Pass", "Ottotto",
+    "OttottoWait", "FlyReflectWall", "FlyReflectCeil", "StopWall", "StopCeil",
+    "MissFoot", "CliffCatch", "CliffWait", "CliffClimbSlow", "CliffClimbQuick",
+    "CliffAttackSlow", "CliffAttackQuick", "CliffEscapeSlow", "CliffEscapeQuick",
+    "CliffJumpSlow1", "CliffJumpSlow2", "CliffJumpQuick1", "CliffJumpQuick2", "AppealR",
+    "AppealL",
+];
+
+/// Fox and Falco special move states, starting at FIRST_CHARACTER_STATE
+const SPACIE_STATES: [&str; 29] = [
+    "BlasterGroundStartup", "BlasterGroundLoop", "BlasterGroundEnd", "BlasterAirStartup",
+    "BlasterAirLoop", "BlasterAirEnd", "IllusionGroundStartup", "IllusionGround",
+    "IllusionGroundEnd", "IllusionAirStartup", "IllusionAir", "IllusionAirEnd",
+    "FireBirdGroundStartup", "FireBirdAirStartup", "FireBirdGround", "FireBirdAir",
+    "FireBirdGroundEnd", "FireBirdAirEnd", "FireBirdBounceEnd", "ReflectorGroundStartup",
+    "ReflectorGroundLoop", "ReflectorGroundReflect", "ReflectorGroundEnd",
+    "ReflectorGroundChangeDirection", "ReflectorAirStartup", "ReflectorAirLoop",
+    "ReflectorAirReflect", "ReflectorAirEnd", "ReflectorAirChangeDirection",
+];
+
+/// Special move states named so far. Only Fox and Falco have tables; every
+/// other character's states from FIRST_CHARACTER_STATE on stay unnamed.
+fn character_states(character: u8) -> &'static [&'static str] {
+    match character {
+        FOX | FALCO => &SPACIE_STATES,
+        _ => &[],
+    }
+}
+
+/// Name of an action state, if known.
+/// States from FIRST_CHARACTER_STATE on are only named when the character is
+/// given, and currently only for Fox and Falco.
+pub fn name(state: u16, character: Option<u8>) -> Option<&'static str> {
+    if state < FIRST_CHARACTER_STATE {
+        return COMMON_STATES.get(state as usize).copied();
+    }
+    let offset = (state - FIRST_CHARACTER_STATE) as usize;
+    character.and_then(|c| character_states(c).get(offset).copied())
+}
+
+/// Name of an action state for display, falling back to its hex ID
+pub fn display_name(state: u16, character: Option<u8>) -> String {
+    match name(state, character) {
+        Some(name) => name.to_string(),
+        None => format!("0x{:X}", state),
+    }
+}
+
+/// Crouching, entering a crouch or standing up out of one
+pub fn is_crouching(state: u16) -> bool {
+    (SQUAT..=SQUAT_RV).contains(&state)
+}
+
+/// Being hit: the damage and tumble states entered on taking a hit
+pub fn is_damaged(state: u16) -> bool {
+    (DAMAGE_HI1..=DAMAGE_FLY_ROLL).contains(&state)
+}
+
+/// Normal attacks (jabs, tilts, smashes, aerials) plus getup, ledge and pummel attacks.
+/// Special moves are character-specific and not included.
+pub fn is_attacking(state: u16) -> bool {
+    (ATTACK_11..=ATTACK_AIR_LW).contains(&state)
+        || matches!(
+            state,
+            DOWN_ATTACK_U | DOWN_ATTACK_D | CATCH_ATTACK | CLIFF_ATTACK_SLOW | CLIFF_ATTACK_QUICK
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_common_names_line_up() {
+        assert_eq!(name(WAIT, None), Some("Wait"));
+        assert_eq!(name(TURN, None), Some("Turn"));
+        assert_eq!(name(DASH, None), Some("Dash"));
+        assert_eq!(name(KNEE_BEND, None), Some("KneeBend"));
+        assert_eq!(name(SQUAT_WAIT, None), Some("SquatWait"));
+        assert_eq!(name(ATTACK_HI3, None), Some("AttackHi3"));
+        assert_eq!(name(ATTACK_HI4, None), Some("AttackHi4"));
+        assert_eq!(name(DOWN_ATTACK_D, None), Some("DownAttackD"));
+        assert_eq!(name(CATCH_ATTACK, None), Some("CatchAttack"));
+        assert_eq!(name(DAMAGE_HI1, None), Some("DamageHi1"));
+        assert_eq!(name(DAMAGE_FLY_ROLL, None), Some("DamageFlyRoll"));
+        assert_eq!(name(GUARD_SET_OFF, None), Some("GuardSetOff"));
+        assert_eq!(name(CLIFF_ATTACK_QUICK, None), Some("CliffAttackQuick"));
+    }
+
+    #[test]
+    fn test_character_states_need_character() {
+        assert_eq!(name(0x168, Some(FOX)), Some("ReflectorGroundStartup"));
+        assert_eq!(name(0x168, Some(FALCO)), Some("ReflectorGroundStartup"));
+        assert_eq!(name(0x168, None), None);
+        // No table for other characters yet
+        assert_eq!(display_name(0x168, Some(9)), "0x168");
+    }
+
+    #[test]
+    fn test_helpers() {
+        assert!(is_crouching(SQUAT_WAIT));
+        assert!(!is_crouching(ATTACK_HI3));
+        assert!(is_attacking(ATTACK_HI3));
+        assert!(is_attacking(CLIFF_ATTACK_SLOW));
+        assert!(!is_attacking(SQUAT_WAIT));
+        assert!(is_damaged(0x50));
+        assert!(!is_damaged(GUARD_SET_OFF));
+    }
+}
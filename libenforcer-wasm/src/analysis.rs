@@ -0,0 +1,34 @@
+use peppi::game::Game;
+use crate::checks::analyze_player_with_ruleset;
+use crate::handwarmer;
+use crate::outcome::game_outcome;
+use crate::parser::extract_all_players;
+use crate::ruleset::Ruleset;
+use crate::teams;
+use crate::types::{GameAnalysis, PlayerAnalysis};
+use std::collections::BTreeMap;
+
+/// Analyze every player in the game along with the game-level verdicts
+/// (handwarmer, outcome, team summaries).
+///
+/// Frames are read once for all players, rather than once per player as
+/// with repeated `extract_player_data` calls. Players that never appear
+/// are left out of `players`.
+pub fn analyze_game(game: &impl Game, ruleset: &Ruleset) -> GameAnalysis {
+    let data = extract_all_players(game);
+    let handwarmer = handwarmer::analyze_players(game, &data);
+    let players: BTreeMap<u8, PlayerAnalysis> = data
+        .iter()
+        .map(|(port, data)| (*port, analyze_player_with_ruleset(data, ruleset)))
+        .collect();
+    let members: Vec<(u8, &PlayerAnalysis)> =
+        players.iter().map(|(port, analysis)| (*port, analysis)).collect();
+
+    GameAnalysis {
+        is_legal: players.values().all(|analysis| analysis.is_legal),
+        teams: teams::summarize(&members),
+        handwarmer,
+        outcome: game_outcome(game),
+        players,
+    }
+}
@@ -0,0 +1,292 @@
+use crate::checks::input_fuzzing::identify_holds;
+use crate::ruleset::Ruleset;
+use crate::types::{
+    AnalogCalibration, AnalogNotch, Coord, FrameUse, GateShape, StickStream, Violation,
+};
+use std::collections::HashMap;
+
+/// Main stick stream this check reads
+pub const STICK_STREAM: StickStream = StickStream::Raw;
+
+/// Frames this check reads
+pub const FRAMES: FrameUse = FrameUse::Present;
+
+/// Width of one gate profile bin in degrees
+const BIN_DEGREES: f64 = 5.0;
+const NUM_BINS: usize = 72;
+
+/// Share of the typical gate radius a hold must reach to count as resting on the rim
+const RIM_FRACTION: f64 = 0.85;
+
+/// Largest angular gap (degrees) between rim holds that still belong to one notch
+const NOTCH_GAP_DEGREES: f64 = 3.0;
+
+/// Minimum rim holds before an angle cluster is considered a notch
+const MIN_NOTCH_HOLDS: usize = 3;
+
+/// Minimum holds before a notch's spread is judged
+const MIN_HOLDS_FOR_REMAP: usize = 10;
+
+/// Share of holds on one exact raw coordinate above which a notch is considered
+/// firmware-remapped. Physical notches jitter by a unit or two between presses.
+const REMAP_CONCENTRATION: f64 = 0.95;
+
+/// Share of bins that must be populated before the gate shape is estimated
+const MIN_GATE_COVERAGE: f64 = 0.5;
+
+/// Corner-to-edge radius ratio above which the gate is considered octagonal
+const OCTAGON_RATIO: f64 = 1.04;
+
+fn angle_of(coord: &Coord) -> f64 {
+    coord.y.atan2(coord.x).to_degrees().rem_euclid(360.0)
+}
+
+fn magnitude_of(coord: &Coord) -> f64 {
+    (coord.x * coord.x + coord.y * coord.y).sqrt()
+}
+
+fn bin_of(angle: f64) -> usize {
+    ((angle / BIN_DEGREES) as usize).min(NUM_BINS - 1)
+}
+
+/// Maximum raw magnitude reached in each 5° bin (0.0 where unvisited)
+pub fn gate_profile(raw_coords: &[Coord]) -> Vec<f64> {
+    let mut radii = vec![0.0; NUM_BINS];
+    for coord in raw_coords {
+        let magnitude = magnitude_of(coord);
+        if magnitude < 1.0 {
+            continue;
+        }
+        let bin = bin_of(angle_of(coord));
+        if magnitude > radii[bin] {
+            radii[bin] = magnitude;
+        }
+    }
+    radii
+}
+
+fn median(values: &mut [f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    Some(values[values.len() / 2])
+}
+
+/// Estimate the gate shape from the profile.
+/// An octagonal gate reaches further at its corners (cardinals and diagonals)
+/// than at the edge midpoints 22.5° away.
+pub fn estimate_gate_shape(radii: &[f64]) -> GateShape {
+    let populated = radii.iter().filter(|&&r| r > 0.0).count();
+    if (populated as f64) < MIN_GATE_COVERAGE * radii.len() as f64 {
+        return GateShape::Unknown;
+    }
+
+    let mean_at = |angles: &mut dyn Iterator<Item = f64>| -> Option<f64> {
+        let values: Vec<f64> = angles.map(|a| radii[bin_of(a)]).filter(|&r| r > 0.0).collect();
+        if values.is_empty() {
+            None
+        } else {
+            Some(values.iter().sum::<f64>() / values.len() as f64)
+        }
+    };
+
+    // Corner bins straddle the corner, so sample just inside it on both sides
+    let corners = mean_at(&mut (0..8).flat_map(|k| {
+        let corner = k as f64 * 45.0;
+        [corner + 1.0, (corner - 1.0).rem_euclid(360.0)]
+    }));
+    let edges = mean_at(&mut (0..8).map(|k| k as f64 * 45.0 + 22.5));
+
+    match (corners, edges) {
+        (Some(c), Some(e)) if c / e >= OCTAGON_RATIO => GateShape::Octagon,
+        (Some(_), Some(_)) => GateShape::Circle,
+        _ => GateShape::Unknown,
+    }
+}
+
+/// Find the angles the stick rests at on the rim and judge whether each
+/// one is a physical notch or a firmware-remapped (snapped) output.
+pub fn detect_notches(raw_coords: &[Coord], radii: &[f64]) -> Vec<AnalogNotch> {
+    let mut populated: Vec<f64> = radii.iter().copied().filter(|&r| r > 0.0).collect();
+    let typical_radius = match median(&mut populated) {
+        Some(r) => r,
+        None => return vec![],
+    };
+    let rim_threshold = typical_radius * RIM_FRACTION;
+
+    let mut rim_holds: Vec<(f64, Coord)> = identify_holds(raw_coords)
+        .into_iter()
+        .filter(|hold| magnitude_of(&hold.coord) >= rim_threshold)
+        .map(|hold| (angle_of(&hold.coord), hold.coord))
+        .collect();
+    rim_holds.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    // Split into clusters wherever consecutive angles are far apart
+    let mut clusters: Vec<Vec<(f64, Coord)>> = Vec::new();
+    for hold in rim_holds {
+        match clusters.last_mut() {
+            Some(cluster) if hold.0 - cluster.last().unwrap().0 <= NOTCH_GAP_DEGREES => {
+                cluster.push(hold)
+            }
+            _ => clusters.push(vec![hold]),
+        }
+    }
+    // Join the clusters straddling 0°/360°
+    if clusters.len() > 1 {
+        let first_angle = clusters[0][0].0;
+        let last_angle = clusters.last().unwrap().last().unwrap().0;
+        if first_angle + 360.0 - last_angle <= NOTCH_GAP_DEGREES {
+            let first = clusters.remove(0);
+            clusters.last_mut().unwrap().extend(first);
+        }
+    }
+
+    clusters
+        .into_iter()
+        .filter(|cluster| cluster.len() >= MIN_NOTCH_HOLDS)
+        .map(|cluster| {
+            let mut counts: HashMap<(i64, i64), (usize, Coord)> = HashMap::new();
+            for (_, coord) in &cluster {
+                let key = (coord.x.round() as i64, coord.y.round() as i64);
+                counts.entry(key).or_insert((0, *coord)).0 += 1;
+            }
+            let (modal_count, modal_coord) = counts
+                .values()
+                .copied()
+                .max_by(|a, b| a.0.cmp(&b.0).then(b.1.x.total_cmp(&a.1.x)))
+                .unwrap();
+
+            let hold_count = cluster.len();
+            let concentration = modal_count as f64 / hold_count as f64;
+
+            AnalogNotch {
+                angle: angle_of(&modal_coord),
+                coord: modal_coord,
+                hold_count,
+                distinct_coords: counts.len(),
+                concentration,
+                remapped: hold_count >= MIN_HOLDS_FOR_REMAP && concentration >= REMAP_CONCENTRATION,
+            }
+        })
+        .collect()
+}
+
+/// Estimate gate shape and notches from raw analog stick values and flag
+/// notch remapping beyond the ruleset's limit.
+pub fn analyze(raw_coords: &[Coord], ruleset: &Ruleset) -> AnalogCalibration {
+    let gate_radii = gate_profile(raw_coords);
+    let gate_shape = estimate_gate_shape(&gate_radii);
+    let notches = detect_notches(raw_coords, &gate_radii);
+
+    let mut violations = Vec::new();
+    let remapped: Vec<&AnalogNotch> = notches.iter().filter(|n| n.remapped).collect();
+
+    if let Some(max) = ruleset.max_remapped_notches {
+        if remapped.len() > max {
+            violations.push(Violation::with_evidence(
+                remapped.len() as f64,
+                format!(
+                    "{} firmware-remapped notches found, ruleset allows {} (angles: {})",
+                    remapped.len(),
+                    max,
+                    remapped
+                        .iter()
+                        .map(|n| format!("{:.1}°", n.angle))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ),
+                remapped.iter().map(|n| n.coord).collect(),
+            ));
+        }
+    }
+
+    AnalogCalibration {
+        pass: violations.is_empty(),
+        gate_shape,
+        gate_radii,
+        notches,
+        violations,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Raw coordinates sweeping the rim of a gate, one per degree
+    fn sweep(radius_at: impl Fn(f64) -> f64) -> Vec<Coord> {
+        (0..360)
+            .map(|deg| {
+                let angle = (deg as f64).to_radians();
+                let r = radius_at(deg as f64);
+                Coord::new(r * angle.cos(), r * angle.sin())
+            })
+            .collect()
+    }
+
+    fn octagon_radius(deg: f64) -> f64 {
+        // Distance to the edge of a regular octagon with corners at multiples of 45°
+        let offset = (deg.rem_euclid(45.0) - 22.5).to_radians();
+        let apothem = 80.0;
+        apothem / offset.cos() * (22.5_f64.to_radians()).cos()
+    }
+
+    fn holds_at(coord: Coord, times: usize) -> Vec<Coord> {
+        let mut coords = Vec::new();
+        for _ in 0..times {
+            coords.push(Coord::new(0.0, 0.0));
+            coords.push(coord);
+            coords.push(coord);
+        }
+        coords
+    }
+
+    #[test]
+    fn test_gate_shape_octagon_vs_circle() {
+        let octagon = gate_profile(&sweep(octagon_radius));
+        assert_eq!(estimate_gate_shape(&octagon), GateShape::Octagon);
+
+        let circle = gate_profile(&sweep(|_| 80.0));
+        assert_eq!(estimate_gate_shape(&circle), GateShape::Circle);
+
+        assert_eq!(estimate_gate_shape(&gate_profile(&[])), GateShape::Unknown);
+    }
+
+    #[test]
+    fn test_physical_notch_has_spread() {
+        let mut coords = sweep(|_| 80.0);
+        for i in 0..12 {
+            coords.extend(holds_at(Coord::new(57.0 + (i % 3) as f64, 57.0 - (i % 2) as f64), 1));
+        }
+
+        let calibration = analyze(&coords, &Ruleset::default());
+        let notch = calibration
+            .notches
+            .iter()
+            .find(|n| (n.angle - 45.0).abs() < 3.0)
+            .expect("diagonal notch should be detected");
+        assert_eq!(notch.hold_count, 12);
+        assert!(notch.distinct_coords > 1);
+        assert!(!notch.remapped);
+    }
+
+    #[test]
+    fn test_remapped_notches_flagged_over_limit() {
+        let mut coords = sweep(|_| 80.0);
+        coords.extend(holds_at(Coord::new(57.0, 57.0), 12));
+        coords.extend(holds_at(Coord::new(-57.0, 57.0), 12));
+
+        let calibration = analyze(&coords, &Ruleset::default());
+        assert!(calibration.pass, "Default ruleset does not limit remapping");
+        assert_eq!(calibration.notches.iter().filter(|n| n.remapped).count(), 2);
+
+        let strict = Ruleset {
+            max_remapped_notches: Some(1),
+            ..Ruleset::default()
+        };
+        let calibration = analyze(&coords, &strict);
+        assert!(!calibration.pass);
+        assert_eq!(calibration.violations[0].evidence.len(), 2);
+    }
+}
@@ -0,0 +1,218 @@
+use crate::checks::input_fuzzing::{coord_key, identify_holds, UNIT};
+use crate::ruleset::Ruleset;
+use crate::types::{Coord, CoordInventory, CoordNotch, FrameUse, StickStream, Violation};
+use std::collections::{HashMap, HashSet};
+
+/// Main stick stream this check reads
+pub const STICK_STREAM: StickStream = StickStream::Processed;
+
+/// Frames this check reads
+pub const FRAMES: FrameUse = FrameUse::Present;
+
+/// How far (in raw units, per axis) a held coordinate may sit from a notch
+/// center and still belong to it. Matches the ±1 fuzzing range.
+const NOTCH_RADIUS: i32 = 1;
+
+/// Most frames listed in a violation reason before truncating
+const MAX_REASON_FRAMES: usize = 10;
+
+/// "held N times at frames ..." for a notch, listing at most MAX_REASON_FRAMES frames
+pub fn holds_summary(notch: &CoordNotch) -> String {
+    let shown: Vec<String> = notch
+        .frames
+        .iter()
+        .take(MAX_REASON_FRAMES)
+        .map(|f| f.to_string())
+        .collect();
+    let more = notch.frames.len().saturating_sub(MAX_REASON_FRAMES);
+    format!(
+        "held {} time{} at frames {}{}",
+        notch.hold_count,
+        if notch.hold_count == 1 { "" } else { "s" },
+        shown.join(", "),
+        if more > 0 { format!(" (+{} more)", more) } else { String::new() },
+    )
+}
+
+fn key_to_coord(key: (i32, i32)) -> Coord {
+    Coord::new(key.0 as f64 * UNIT, key.1 as f64 * UNIT)
+}
+
+/// Group held coordinates into the controller's modifier notches.
+///
+/// Algorithm:
+/// 1. Collect every hold (2+ identical consecutive frames) by integer key
+/// 2. Visit keys from most to least held; each unclaimed key becomes a notch center
+/// 3. The center claims all unclaimed keys within NOTCH_RADIUS, absorbing fuzz offsets
+///
+/// Notches are returned most-held first. `frame_numbers` holds the game frame
+/// of each coordinate, so hold frames can be reported as game frames.
+pub fn cluster_notches(coords: &[Coord], frame_numbers: &[i32]) -> Vec<CoordNotch> {
+    let holds = identify_holds(coords);
+
+    let mut key_frames: HashMap<(i32, i32), Vec<i32>> = HashMap::new();
+    for hold in &holds {
+        key_frames
+            .entry(coord_key(&hold.coord))
+            .or_default()
+            .push(frame_numbers[hold.start_frame]);
+    }
+
+    // Most-held first; ties broken by key so the result is deterministic
+    let mut keys: Vec<(i32, i32)> = key_frames.keys().copied().collect();
+    keys.sort_by(|a, b| key_frames[b].len().cmp(&key_frames[a].len()).then(a.cmp(b)));
+
+    let mut claimed: HashSet<(i32, i32)> = HashSet::new();
+    let mut notches: Vec<CoordNotch> = Vec::new();
+
+    for key in keys {
+        if claimed.contains(&key) {
+            continue;
+        }
+
+        let center = key_to_coord(key);
+        let mut frames = Vec::new();
+        for ox in -NOTCH_RADIUS..=NOTCH_RADIUS {
+            for oy in -NOTCH_RADIUS..=NOTCH_RADIUS {
+                let nkey = (key.0 + ox, key.1 + oy);
+                if claimed.contains(&nkey) {
+                    continue;
+                }
+                if let Some(nframes) = key_frames.get(&nkey) {
+                    claimed.insert(nkey);
+                    frames.extend_from_slice(nframes);
+                }
+            }
+        }
+        frames.sort_unstable();
+
+        notches.push(CoordNotch {
+            coord: center,
+            angle: center.y.atan2(center.x).to_degrees(),
+            magnitude: (center.x * center.x + center.y * center.y).sqrt(),
+            hold_count: frames.len(),
+            frames,
+        });
+    }
+
+    notches
+}
+
+/// Is the notch center within fuzz range of an allowed coordinate?
+/// Coordinates are compared by absolute value, so one allowed entry covers all four quadrants.
+fn is_allowed(coord: &Coord, allowed: &[Coord]) -> bool {
+    let (cx, cy) = coord_key(&Coord::new(coord.x.abs(), coord.y.abs()));
+    allowed.iter().any(|a| {
+        let (ax, ay) = coord_key(&Coord::new(a.x.abs(), a.y.abs()));
+        (cx - ax).abs() <= NOTCH_RADIUS && (cy - ay).abs() <= NOTCH_RADIUS
+    })
+}
+
+/// Build the player's notch inventory and, if the ruleset has a coordinate
+/// whitelist, flag every notch outside of it. The origin is always allowed.
+pub fn analyze(coords: &[Coord], frame_numbers: &[i32], ruleset: &Ruleset) -> CoordInventory {
+    let notches = cluster_notches(coords, frame_numbers);
+    let mut violations = Vec::new();
+
+    if let Some(allowed) = &ruleset.allowed_coordinates {
+        for notch in &notches {
+            if coord_key(&notch.coord) == (0, 0) || is_allowed(&notch.coord, allowed) {
+                continue;
+            }
+
+            violations.push(Violation::with_evidence(
+                notch.frames.first().copied().unwrap_or(0) as f64,
+                format!(
+                    "Coordinate ({:.4}, {:.4}) is not in the allowed set — {}",
+                    notch.coord.x,
+                    notch.coord.y,
+                    holds_summary(notch),
+                ),
+                vec![notch.coord],
+            ));
+        }
+    }
+
+    CoordInventory {
+        pass: violations.is_empty(),
+        notches,
+        violations,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{game_frames, make_targeting_sequence};
+
+    #[test]
+    fn test_fuzzed_holds_cluster_into_one_notch() {
+        let mut outputs = Vec::new();
+        for _ in 0..10 { outputs.push(Coord::new(0.5, 0.5)); }
+        for _ in 0..4 { outputs.push(Coord::new(0.5 + UNIT, 0.5)); }
+        for _ in 0..4 { outputs.push(Coord::new(0.5, 0.5 - UNIT)); }
+
+        let coords = make_targeting_sequence(&outputs);
+        let notches = cluster_notches(&coords, &game_frames(coords.len()));
+        // Origin plus the single fuzzed notch
+        assert_eq!(notches.len(), 2);
+        let notch = notches.iter().find(|n| coord_key(&n.coord) == (40, 40)).unwrap();
+        assert_eq!(notch.hold_count, 18);
+        assert!((notch.angle - 45.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_no_whitelist_always_passes() {
+        let coords = make_targeting_sequence(&[Coord::new(0.5, 0.5); 5]);
+        let inventory = analyze(&coords, &game_frames(coords.len()), &Ruleset::default());
+        assert!(inventory.pass);
+        assert!(!inventory.notches.is_empty());
+    }
+
+    #[test]
+    fn test_whitelist_flags_extra_notch() {
+        let mut outputs = vec![Coord::new(0.5, 0.5); 5];
+        outputs.extend(vec![Coord::new(-0.3, 0.6); 3]);
+        let coords = make_targeting_sequence(&outputs);
+
+        let ruleset = Ruleset {
+            allowed_coordinates: Some(vec![Coord::new(0.5, 0.5)]),
+            ..Ruleset::default()
+        };
+        let inventory = analyze(&coords, &game_frames(coords.len()), &ruleset);
+        assert!(!inventory.pass);
+        assert_eq!(inventory.violations.len(), 1);
+        assert_eq!(inventory.violations[0].metric, (28 - 123) as f64);
+        assert!(inventory.violations[0].reason.contains("held 3 times at frames -95, -90, -85"));
+    }
+
+    #[test]
+    fn test_frames_skip_absent_frames() {
+        let coords = make_targeting_sequence(&[Coord::new(-0.3, 0.6)]);
+        // The player was absent for 100 frames before these inputs
+        let frame_numbers: Vec<i32> = game_frames(coords.len()).iter().map(|f| f + 100).collect();
+        let ruleset = Ruleset {
+            allowed_coordinates: Some(vec![Coord::new(0.5, 0.5)]),
+            ..Ruleset::default()
+        };
+        let inventory = analyze(&coords, &frame_numbers, &ruleset);
+        assert_eq!(inventory.violations[0].metric, (3 - 123 + 100) as f64);
+    }
+
+    #[test]
+    fn test_whitelist_is_quadrant_symmetric() {
+        let outputs = vec![
+            Coord::new(0.5, 0.5),
+            Coord::new(-0.5, 0.5),
+            Coord::new(-0.5 - UNIT, -0.5),
+            Coord::new(0.5, -0.5),
+        ];
+        let coords = make_targeting_sequence(&outputs);
+
+        let ruleset = Ruleset {
+            allowed_coordinates: Some(vec![Coord::new(0.5, 0.5)]),
+            ..Ruleset::default()
+        };
+        assert!(analyze(&coords, &game_frames(coords.len()), &ruleset).pass);
+    }
+}
@@ -1,49 +1,33 @@
-use crate::types::{CheckResult, Coord, Violation};
+use super::transitions;
+use crate::types::{CheckResult, Coord, FrameUse, StickStream, TransitionAnalysis};
+
+/// Main stick stream this check reads for evidence and exemptions.
+/// Verdicts rest on action states, so approximated sticks don't degrade it.
+pub const STICK_STREAM: StickStream = StickStream::Processed;
+
+/// Frames this check reads
+pub const FRAMES: FrameUse = FrameUse::Aligned;
 
 /// Check for impossibly fast crouch-to-uptilt transitions
 /// Human reaction time makes transitions <3 frames impossible
 pub fn check(coords: &[Coord], action_states: &[u16]) -> CheckResult {
-    const CROUCH_STATE: u16 = 0x28;
-    const UPTILT_STATE: u16 = 0x38;
-
-    let mut violations = Vec::new();
-    let mut last_crouch_frame: i32 = -124;
-
-    for (i, &action_state) in action_states.iter().enumerate() {
-        let frame_number = i as i32 - 123; // Frames start at -123
-
-        // Track when player is crouching
-        if action_state == CROUCH_STATE {
-            last_crouch_frame = frame_number;
-        }
-
-        // Check for uptilt
-        if action_state == UPTILT_STATE {
-            let frames_since_crouch = frame_number - last_crouch_frame;
+    let result = transitions::evaluate(&transitions::crouch_uptilt_rule(), coords, &[], action_states, None);
 
-            // If uptilt occurs within 3 frames of crouch, it's suspicious
-            if frames_since_crouch <= 3 {
-                // Get evidence coordinates (4 frames worth)
-                let evidence_start = (last_crouch_frame + 123) as usize;
-                let evidence_end = (evidence_start + 4).min(coords.len());
-                let evidence = coords[evidence_start..evidence_end].to_vec();
-
-                violations.push(Violation::with_evidence(
-                    last_crouch_frame as f64,
-                    format!(
-                        "Crouch-uptilt occurred within {} frames (frame {} to {})",
-                        frames_since_crouch, last_crouch_frame, frame_number
-                    ),
-                    evidence,
-                ));
-            }
-        }
-    }
-
-    if violations.is_empty() {
+    if result.pass {
         CheckResult::pass()
     } else {
-        CheckResult::fail(violations)
+        CheckResult::fail(result.violations)
+    }
+}
+
+/// Crouch-uptilt result as reported by the transition rules, so the
+/// transition is only evaluated once per player. Passes when the ruleset
+/// drops the rule.
+pub fn from_transitions(transitions: &TransitionAnalysis) -> CheckResult {
+    let name = transitions::crouch_uptilt_rule().name;
+    match transitions.rules.iter().find(|rule| rule.name == name) {
+        Some(rule) if !rule.pass => CheckResult::fail(rule.violations.clone()),
+        _ => CheckResult::pass(),
     }
 }
 
@@ -62,7 +46,7 @@ mod tests {
         action_states[133] = 0x38; // Frame 10
 
         let result = check(&coords, &action_states);
-        assert_eq!(result.result, false);
+        assert!(!result.result);
     }
 
     #[test]
@@ -76,7 +60,7 @@ mod tests {
         action_states[125] = 0x38; // Frame 2
 
         let result = check(&coords, &action_states);
-        assert_eq!(result.result, true);
-        assert!(result.details.len() > 0);
+        assert!(result.result);
+        assert!(!result.details.is_empty());
     }
 }
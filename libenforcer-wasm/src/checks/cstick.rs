@@ -0,0 +1,217 @@
+use crate::checks::coord_inventory::{cluster_notches, holds_summary};
+use crate::checks::input_fuzzing::coord_key;
+use crate::checks::travel_time;
+use crate::ruleset::Ruleset;
+use crate::types::{CStickAnalysis, Coord, CoordNotch, FrameUse, Violation};
+use crate::utils::float_equals;
+
+/// Frames this check reads
+pub const FRAMES: FrameUse = FrameUse::Present;
+
+/// How far (degrees) a notch's angle may sit from an allowed angle.
+/// One raw unit of fuzz on the rim moves the angle by under a degree.
+const ANGLE_TOLERANCE: f64 = 1.5;
+
+/// Distinct non-cardinal C-stick notches (folded into one quadrant) at which a
+/// box is using an analog C-stick modifier. Legal firmwares offer at most two
+/// angled C-stick outputs, mirrored across the quadrants.
+const ANALOG_MODIFIER_MIN_NOTCHES: usize = 3;
+
+/// Angle of a coordinate folded into the first quadrant (0-90 degrees)
+fn folded_angle(coord: &Coord) -> f64 {
+    coord.y.abs().atan2(coord.x.abs()).to_degrees()
+}
+
+fn is_cardinal_or_origin(notch: &CoordNotch) -> bool {
+    let (x, y) = coord_key(&notch.coord);
+    x == 0 || y == 0
+}
+
+fn notch_violation(notch: &CoordNotch, problem: String) -> Violation {
+    Violation::with_evidence(
+        notch.frames.first().copied().unwrap_or(0) as f64,
+        format!(
+            "C-Stick coordinate ({:.4}, {:.4}) {} — {}",
+            notch.coord.x,
+            notch.coord.y,
+            problem,
+            holds_summary(notch)
+        ),
+        vec![notch.coord],
+    )
+}
+
+/// Analyze a box player's C-stick: notch inventory, forbidden axis values,
+/// allowed angles, travel time and analog C-stick modifiers. C-stick fuzzing
+/// is analyzed per channel by `input_fuzzing::analyze_channel`.
+///
+/// Values and angles are compared by absolute value, so one entry covers all
+/// four quadrants. The travel rate only fails when the ruleset sets a minimum,
+/// since box C-sticks usually jump straight to their target.
+pub fn analyze(c_coords: &[Coord], frame_numbers: &[i32], ruleset: &Ruleset) -> CStickAnalysis {
+    let notches = cluster_notches(c_coords, frame_numbers);
+    let travel_rate = travel_time::average_travel_coord_hit_rate(c_coords);
+    let mut violations = Vec::new();
+
+    for notch in &notches {
+        let x = notch.coord.x.abs();
+        let y = notch.coord.y.abs();
+        if let Some(value) = ruleset.forbidden_cstick_x.iter().find(|v| float_equals(x, v.abs())) {
+            violations.push(notch_violation(notch, format!("uses forbidden x value {}", value)));
+        }
+        if let Some(value) = ruleset.forbidden_cstick_y.iter().find(|v| float_equals(y, v.abs())) {
+            violations.push(notch_violation(notch, format!("uses forbidden y value {}", value)));
+        }
+
+        if let Some(allowed) = &ruleset.allowed_cstick_angles {
+            let angle = folded_angle(&notch.coord);
+            let is_allowed = is_cardinal_or_origin(notch)
+                || allowed.iter().any(|a| {
+                    let a = folded_angle(&Coord::new(a.to_radians().cos(), a.to_radians().sin()));
+                    (angle - a).abs() <= ANGLE_TOLERANCE
+                });
+            if !is_allowed {
+                violations.push(notch_violation(
+                    notch,
+                    format!("is at {:.1}°, not an allowed angle", angle),
+                ));
+            }
+        }
+    }
+
+    // Fold non-cardinal notches into one quadrant; an analog modifier lands on many
+    let mut angled: Vec<(i32, i32)> = notches
+        .iter()
+        .filter(|notch| !is_cardinal_or_origin(notch))
+        .map(|notch| {
+            let (x, y) = coord_key(&notch.coord);
+            (x.abs(), y.abs())
+        })
+        .collect();
+    angled.sort_unstable();
+    angled.dedup_by(|a, b| (a.0 - b.0).abs() <= 1 && (a.1 - b.1).abs() <= 1);
+    let analog_modifier = angled.len() >= ANALOG_MODIFIER_MIN_NOTCHES;
+    if analog_modifier {
+        let first = notches
+            .iter()
+            .filter(|notch| !is_cardinal_or_origin(notch))
+            .filter_map(|notch| notch.frames.first().copied())
+            .min()
+            .unwrap_or(0);
+        violations.push(Violation::with_evidence(
+            first as f64,
+            format!(
+                "C-Stick held {} distinct angled coordinates, an analog C-Stick modifier",
+                angled.len()
+            ),
+            notches
+                .iter()
+                .filter(|notch| !is_cardinal_or_origin(notch))
+                .map(|notch| notch.coord)
+                .collect(),
+        ));
+    }
+
+    if let Some(min_rate) = ruleset.min_cstick_travel_rate {
+        if travel_rate < min_rate {
+            violations.push(Violation::new(
+                travel_rate,
+                format!(
+                    "C-Stick travel coordinate hit rate {:.1}% is below {:.1}%",
+                    travel_rate * 100.0,
+                    min_rate * 100.0
+                ),
+            ));
+        }
+    }
+
+    CStickAnalysis {
+        pass: violations.is_empty(),
+        notches,
+        travel_rate,
+        analog_modifier,
+        violations,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::game_frames;
+
+    /// Neutral hold → output hold, for each output (box C-sticks have no travel)
+    fn make_sequence(outputs: &[Coord]) -> Vec<Coord> {
+        let mut coords = Vec::new();
+        for &output in outputs {
+            coords.extend([Coord::new(0.0, 0.0), Coord::new(0.0, 0.0), output, output]);
+        }
+        coords
+    }
+
+    #[test]
+    fn test_legal_outputs_pass() {
+        // Cardinals and the angled outputs legal firmwares offer
+        let coords = make_sequence(&[
+            Coord::new(1.0, 0.0),
+            Coord::new(0.0, -1.0),
+            Coord::new(-0.525, 0.85),
+            Coord::new(-0.525, -0.85),
+        ]);
+        let analysis = analyze(&coords, &game_frames(coords.len()), &Ruleset::default());
+        assert!(analysis.pass);
+        assert!(!analysis.analog_modifier);
+        assert_eq!(analysis.travel_rate, 0.0);
+    }
+
+    #[test]
+    fn test_forbidden_y_value() {
+        let coords = make_sequence(&[Coord::new(0.5, -0.6625); 2]);
+        let analysis = analyze(&coords, &game_frames(coords.len()), &Ruleset::default());
+        assert!(!analysis.pass);
+        assert_eq!(analysis.violations.len(), 1);
+        assert!(analysis.violations[0].reason.contains("forbidden y value 0.6625"));
+        assert!(analysis.violations[0].reason.contains("held 2 times"));
+    }
+
+    #[test]
+    fn test_allowed_angles() {
+        let coords = make_sequence(&[Coord::new(-0.525, 0.85), Coord::new(0.7, 0.7)]);
+        let ruleset = Ruleset {
+            allowed_cstick_angles: Some(vec![58.3]),
+            ..Ruleset::default()
+        };
+        let analysis = analyze(&coords, &game_frames(coords.len()), &ruleset);
+        assert_eq!(analysis.violations.len(), 1);
+        assert!(analysis.violations[0].reason.contains("45.0°"));
+    }
+
+    #[test]
+    fn test_analog_modifier() {
+        let coords = make_sequence(&[
+            Coord::new(0.7375, 0.6625),
+            Coord::new(0.775, 0.6125),
+            Coord::new(0.75, 0.6375),
+        ]);
+        let ruleset = Ruleset {
+            forbidden_cstick_x: vec![],
+            forbidden_cstick_y: vec![],
+            ..Ruleset::default()
+        };
+        let analysis = analyze(&coords, &game_frames(coords.len()), &ruleset);
+        assert!(analysis.analog_modifier);
+        assert_eq!(analysis.violations.len(), 1);
+        assert_eq!(analysis.violations[0].evidence.len(), 3);
+    }
+
+    #[test]
+    fn test_min_travel_rate() {
+        let coords = make_sequence(&[Coord::new(1.0, 0.0); 3]);
+        let ruleset = Ruleset {
+            min_cstick_travel_rate: Some(0.25),
+            ..Ruleset::default()
+        };
+        let analysis = analyze(&coords, &game_frames(coords.len()), &ruleset);
+        assert!(!analysis.pass);
+        assert!(analysis.violations[0].reason.contains("below 25.0%"));
+    }
+}
@@ -1,6 +1,9 @@
-use crate::types::{CheckResult, Coord, Violation};
+use crate::types::{CheckResult, Coord, FrameUse, Violation};
 use crate::utils::float_equals;
 
+/// Frames this check reads
+pub const FRAMES: FrameUse = FrameUse::Present;
+
 /// Check for disallowed C-stick coordinate values
 /// Certain exact values indicate controller hardware manipulation
 pub fn check(c_coords: &[Coord]) -> CheckResult {
@@ -46,7 +49,7 @@ mod tests {
         ];
 
         let result = get_cstick_violations(&coords);
-        assert_eq!(result.result, true);
+        assert!(result.result);
         assert_eq!(result.details.len(), 2);
     }
 
@@ -59,6 +62,6 @@ mod tests {
         ];
 
         let result = get_cstick_violations(&coords);
-        assert_eq!(result.result, false);
+        assert!(!result.result);
     }
 }
@@ -0,0 +1,261 @@
+use crate::types::{Coord, CoordNotch, FirmwareFingerprint, FirmwareMatch, FirmwareProfile, FuzzAnalysis};
+
+/// How far (in raw units, per axis) a notch may sit from a profile coordinate
+/// and still match it. Matches the ±1 fuzzing range.
+const MATCH_RADIUS: i32 = 1;
+
+/// Fuzz events needed before the fuzzing behavior is used for matching
+const MIN_FUZZ_EVENTS: usize = 20;
+
+/// Score difference below which two profiles are tied
+const TIE_EPSILON: f64 = 1e-9;
+
+// Score weights (sum to 1.0)
+const WEIGHT_EXPLAINED: f64 = 0.6;
+const WEIGHT_COVERAGE: f64 = 0.2;
+const WEIGHT_FUZZ: f64 = 0.2;
+
+/// Modifier notches of the B0XX layout's default Melee mode (first quadrant,
+/// raw units of 1/80): full diagonal, ModX and ModY notches.
+const B0XX_LAYOUT: [(i32, i32); 7] = [
+    (56, 56), // full diagonal
+    (53, 0),  // ModX horizontal
+    (0, 43),  // ModX vertical
+    (59, 25), // ModX diagonal
+    (27, 0),  // ModY horizontal
+    (0, 59),  // ModY vertical
+    (25, 59), // ModY diagonal
+];
+
+/// Extra angled notches (ModX/ModY + C-button firefox angles) in the HayBox layout's Melee mode
+const HAYBOX_ANGLES: [(i32, i32); 4] = [(63, 37), (45, 63), (67, 31), (31, 67)];
+
+/// Whether the player's outputs look fuzzed, if there is enough data to say
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FuzzState {
+    Fuzzed,
+    Unfuzzed,
+}
+
+fn fuzz_state(fuzz: &FuzzAnalysis) -> Option<FuzzState> {
+    if fuzz.total_fuzz_events < MIN_FUZZ_EVENTS {
+        None
+    } else if fuzz.pass && fuzz.llr_score > 0.0 {
+        Some(FuzzState::Fuzzed)
+    } else {
+        Some(FuzzState::Unfuzzed)
+    }
+}
+
+fn profile(name: &str, fuzzed: bool, extra: &[(i32, i32)]) -> FirmwareProfile {
+    let coordinates = B0XX_LAYOUT
+        .iter()
+        .chain(extra.iter())
+        .map(|&(x, y)| Coord::new(x as f64 / 80.0, y as f64 / 80.0))
+        .collect();
+    FirmwareProfile {
+        name: name.to_string(),
+        coordinates,
+        fuzzed,
+    }
+}
+
+/// The bundled catalog: each modifier layout family, with and without input fuzzing.
+///
+/// Profiles describe layout families, not firmwares or versions. Any firmware
+/// shipping a family's notches matches it, so a match can't confirm a claimed
+/// firmware version, only whether the outputs fit the claimed layout.
+pub fn bundled_profiles() -> Vec<FirmwareProfile> {
+    vec![
+        profile("B0XX layout (unfuzzed)", false, &[]),
+        profile("B0XX layout (fuzzed)", true, &[]),
+        profile("HayBox layout (unfuzzed)", false, &HAYBOX_ANGLES),
+        profile("HayBox layout (fuzzed)", true, &HAYBOX_ANGLES),
+    ]
+}
+
+/// Fold a coordinate into the first quadrant as integer raw units
+fn folded_key(coord: &Coord) -> (i32, i32) {
+    (
+        (coord.x.abs() * 80.0).round() as i32,
+        (coord.y.abs() * 80.0).round() as i32,
+    )
+}
+
+fn keys_match(a: (i32, i32), b: (i32, i32)) -> bool {
+    (a.0 - b.0).abs() <= MATCH_RADIUS && (a.1 - b.1).abs() <= MATCH_RADIUS
+}
+
+/// Score one profile against the player's notches.
+///
+/// - explained: share of holds (excluding the origin) landing on a profile coordinate or cardinal
+/// - coverage: share of profile coordinates the player actually used
+/// - fuzz: 1.0 if fuzzing behavior agrees, 0.0 if it contradicts, 0.5 if unknown
+fn score_profile(
+    notches: &[CoordNotch],
+    fuzz: Option<FuzzState>,
+    profile: &FirmwareProfile,
+) -> FirmwareMatch {
+    let mut profile_keys: Vec<(i32, i32)> = profile.coordinates.iter().map(folded_key).collect();
+    // Every firmware outputs full cardinals
+    profile_keys.push((80, 0));
+    profile_keys.push((0, 80));
+
+    let mut total_holds = 0usize;
+    let mut explained_holds = 0usize;
+    let mut used = vec![false; profile_keys.len()];
+    let mut unexplained_coords = Vec::new();
+
+    for notch in notches {
+        let key = folded_key(&notch.coord);
+        if key == (0, 0) {
+            continue;
+        }
+        total_holds += notch.hold_count;
+
+        match profile_keys.iter().position(|&pkey| keys_match(key, pkey)) {
+            Some(idx) => {
+                explained_holds += notch.hold_count;
+                used[idx] = true;
+            }
+            None => unexplained_coords.push(notch.coord),
+        }
+    }
+
+    let explained = if total_holds == 0 {
+        0.0
+    } else {
+        explained_holds as f64 / total_holds as f64
+    };
+    let coverage = used.iter().filter(|&&u| u).count() as f64 / used.len() as f64;
+    let fuzz_consistent = fuzz.map(|state| (state == FuzzState::Fuzzed) == profile.fuzzed);
+    let fuzz_score = match fuzz_consistent {
+        Some(true) => 1.0,
+        Some(false) => 0.0,
+        None => 0.5,
+    };
+
+    FirmwareMatch {
+        name: profile.name.clone(),
+        score: WEIGHT_EXPLAINED * explained + WEIGHT_COVERAGE * coverage + WEIGHT_FUZZ * fuzz_score,
+        explained,
+        coverage,
+        fuzz_consistent,
+        unexplained_coords,
+    }
+}
+
+/// Compare a player's notch inventory and fuzz statistics against a catalog.
+/// Matches are returned best first, with any profiles tied for best listed in `tied`.
+pub fn fingerprint_with_catalog(
+    notches: &[CoordNotch],
+    fuzz: &FuzzAnalysis,
+    catalog: &[FirmwareProfile],
+) -> FirmwareFingerprint {
+    let state = fuzz_state(fuzz);
+    let mut matches: Vec<FirmwareMatch> = catalog
+        .iter()
+        .map(|profile| score_profile(notches, state, profile))
+        .collect();
+    matches.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+
+    let tied = match matches.first() {
+        Some(best) => matches
+            .iter()
+            .filter(|m| best.score - m.score < TIE_EPSILON)
+            .map(|m| m.name.clone())
+            .collect(),
+        None => Vec::new(),
+    };
+
+    FirmwareFingerprint { matches, tied }
+}
+
+/// Compare a player's notch inventory and fuzz statistics against the bundled catalog
+pub fn fingerprint(notches: &[CoordNotch], fuzz: &FuzzAnalysis) -> FirmwareFingerprint {
+    fingerprint_with_catalog(notches, fuzz, &bundled_profiles())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notch(x: i32, y: i32, hold_count: usize) -> CoordNotch {
+        let coord = Coord::new(x as f64 / 80.0, y as f64 / 80.0);
+        CoordNotch {
+            coord,
+            angle: coord.y.atan2(coord.x).to_degrees(),
+            magnitude: (coord.x * coord.x + coord.y * coord.y).sqrt(),
+            hold_count,
+            frames: vec![],
+        }
+    }
+
+    fn fuzz_analysis(pass: bool, llr_score: f64, total_fuzz_events: usize) -> FuzzAnalysis {
+        FuzzAnalysis {
+            pass,
+            llr_score,
+            p_value_x: None,
+            p_value_y: None,
+            total_fuzz_events,
+            observed_x: [0, 0, 0],
+            observed_y: [0, 0, 0],
+            violations: vec![],
+        }
+    }
+
+    #[test]
+    fn test_fuzzing_separates_identical_layouts() {
+        let notches = vec![notch(0, 0, 50), notch(56, 56, 10), notch(-53, 0, 10), notch(59, -25, 5)];
+
+        let fuzzed = fingerprint(&notches, &fuzz_analysis(true, 0.8, 100));
+        assert!(fuzzed.matches[0].name.contains("(fuzzed)"));
+        assert_eq!(fuzzed.matches[0].fuzz_consistent, Some(true));
+        assert!((fuzzed.matches[0].explained - 1.0).abs() < 1e-9);
+
+        let unfuzzed = fingerprint(&notches, &fuzz_analysis(false, -0.6, 100));
+        assert!(unfuzzed.matches[0].name.contains("(unfuzzed)"));
+        assert_eq!(unfuzzed.tied.len(), 1);
+    }
+
+    #[test]
+    fn test_bundled_profiles_are_distinct() {
+        let profiles = bundled_profiles();
+        for (i, a) in profiles.iter().enumerate() {
+            for b in &profiles[i + 1..] {
+                let same_coords = a.coordinates.len() == b.coordinates.len()
+                    && a.coordinates.iter().zip(&b.coordinates).all(|(p, q)| p == q);
+                assert!(
+                    !(same_coords && a.fuzzed == b.fuzzed),
+                    "{} and {} can't be told apart",
+                    a.name,
+                    b.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_unknown_fuzzing_reports_tie() {
+        let notches = vec![notch(56, 56, 10), notch(-53, 0, 10)];
+        let result = fingerprint(&notches, &fuzz_analysis(true, 0.0, 5));
+        assert_eq!(result.tied, vec!["B0XX layout (unfuzzed)", "B0XX layout (fuzzed)"]);
+    }
+
+    #[test]
+    fn test_angled_notches_prefer_haybox() {
+        let notches = vec![notch(56, 56, 10), notch(63, 37, 8), notch(-45, 63, 8)];
+        let result = fingerprint(&notches, &fuzz_analysis(true, 0.8, 100));
+        assert_eq!(result.matches[0].name, "HayBox layout (fuzzed)");
+        assert!(result.matches[0].unexplained_coords.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_coords_are_reported() {
+        let notches = vec![notch(56, 56, 10), notch(10, 70, 10)];
+        let result = fingerprint(&notches, &fuzz_analysis(true, 0.0, 5));
+        assert!((result.matches[0].explained - 0.5).abs() < 1e-9);
+        assert_eq!(result.matches[0].unexplained_coords.len(), 1);
+        assert_eq!(result.matches[0].fuzz_consistent, None);
+    }
+}
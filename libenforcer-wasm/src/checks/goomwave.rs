@@ -1,84 +1,142 @@
-use crate::types::{CheckResult, Coord};
+use crate::checks::snapping::{measure_axis, normalize_raw};
+use crate::types::{CheckResult, Coord, FrameUse, SnapAxis, SnapKind, StickStream, Violation};
+
+/// Main stick stream this check reads. Clamping happens in the controller,
+/// so it is measured on the raw hardware values.
+pub const STICK_STREAM: StickStream = StickStream::Raw;
+
+/// Frames this check reads
+pub const FRAMES: FrameUse = FrameUse::Present;
+
+/// Minimum coordinates on the cardinals before clamping is judged
+const MIN_CLAMPED_POINTS: usize = 30;
+
+/// Share of the cardinal bands that must sit exactly on the cardinals.
+/// Natural sticks stay below 0.3 and notched sticks (orca dataset) below 0.97,
+/// while GoomWave reaches 1.0, so a few stray inputs don't hide it.
+const CLAMPED_SCORE_THRESHOLD: f64 = 0.98;
+
+/// Measure the four cardinals with the snapping detector
+fn cardinal_axes(coords: &[Coord]) -> Vec<SnapAxis> {
+    (0..4)
+        .map(|k| measure_axis(coords, k as f64 * 90.0, SnapKind::Cardinal))
+        .collect()
+}
+
+/// Share of the coordinates in the cardinal bands that land on the cardinals
+fn clamped_score(axes: &[SnapAxis]) -> f64 {
+    let on_axis: usize = axes.iter().map(|a| a.on_axis).sum();
+    let near_axis: usize = axes.iter().map(|a| a.near_axis).sum();
+    if on_axis + near_axis == 0 {
+        0.0
+    } else {
+        on_axis as f64 / (on_axis + near_axis) as f64
+    }
+}
+
+fn is_clamped(axes: &[SnapAxis]) -> bool {
+    let on_axis: usize = axes.iter().map(|a| a.on_axis).sum();
+    on_axis >= MIN_CLAMPED_POINTS && clamped_score(axes) >= CLAMPED_SCORE_THRESHOLD
+}
 
 /// Check for GoomWave hardware modification
 /// GoomWave clamps small stick movements to cardinal directions
-/// Detection: absence of coordinates with small off-axis values
+/// Detection: plenty of coordinates on the cardinals, almost none in the bands beside them
 pub fn check(coords: &[Coord]) -> CheckResult {
-    if has_goomwave_clamping(coords) {
-        return CheckResult::fail_single(
-            0.0,
-            "No coordinates found with small off-axis values (characteristic of GoomWave clamping)".to_string(),
-        );
+    let axes = cardinal_axes(coords);
+    if !is_clamped(&axes) {
+        return CheckResult::pass();
     }
 
-    CheckResult::pass()
+    let on_axis: usize = axes.iter().map(|a| a.on_axis).sum();
+    let near_axis: usize = axes.iter().map(|a| a.near_axis).sum();
+    let evidence: Vec<Coord> = axes
+        .iter()
+        .flat_map(|a| a.frames.iter().map(|&f| coords[f]))
+        .collect();
+    let first_frame = axes.iter().filter_map(|a| a.frames.first()).min().copied().unwrap_or(0);
+
+    CheckResult::fail(vec![Violation::with_evidence(
+        first_frame as f64,
+        format!(
+            "{} coordinates on the cardinals and {} with small off-axis values ({:.0}% clamped, characteristic of GoomWave)",
+            on_axis,
+            near_axis,
+            clamped_score(&axes) * 100.0
+        ),
+        evidence,
+    )])
+}
+
+/// `check` on raw int8-scale stick values
+pub fn check_raw(raw_coords: &[Coord]) -> CheckResult {
+    check(&normalize_raw(raw_coords))
 }
 
 /// Check for GoomWave cardinal clamping pattern
-/// Returns true if coords show evidence of clamping (no small off-axis values)
+/// Returns true if the bands beside the cardinals are nearly empty while the cardinals themselves are well used
 pub fn has_goomwave_clamping(coords: &[Coord]) -> bool {
-    const THRESHOLD: f64 = 0.08;
-
-    for coord in coords {
-        // Skip coordinates on cardinal axes (x=0 or y=0)
-        if coord.x.abs() < 0.0001 || coord.y.abs() < 0.0001 {
-            continue;
-        }
-
-        // If we find any coord with small X or Y value, it's natural
-        if coord.x.abs() < THRESHOLD || coord.y.abs() < THRESHOLD {
-            return false;
-        }
-    }
-
-    true
+    is_clamped(&cardinal_axes(coords))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Sweep the stick through every angle at several tilts. With `clamp`, any
+    /// coordinate within that distance of a cardinal snaps onto it.
+    fn sweep(clamp: f64) -> Vec<Coord> {
+        let mut coords: Vec<Coord> = Vec::new();
+        for radius in [0.45, 0.6, 0.75] {
+            for i in 0..720 {
+                let angle = (i as f64 * 0.5).to_radians();
+                let mut x = radius * angle.cos();
+                let mut y = radius * angle.sin();
+                if x.abs() < clamp {
+                    x = 0.0;
+                }
+                if y.abs() < clamp {
+                    y = 0.0;
+                }
+                coords.push(Coord { x, y });
+            }
+        }
+        coords
+    }
+
     #[test]
     fn test_natural_analog() {
-        // Has small off-axis values - should pass
-        let coords = vec![
-            Coord { x: 0.05, y: 0.5 }, // Small X
-            Coord { x: 0.5, y: 0.5 },
-            Coord { x: 1.0, y: 0.03 }, // Small Y
-        ];
+        // Natural stick: passes through the bands beside the cardinals, and
+        // still lands exactly on them often enough for clamping to be judged
+        let mut coords = sweep(0.0);
+        for _ in 0..10 {
+            coords.extend([
+                Coord { x: 0.6, y: 0.0 },
+                Coord { x: 0.0, y: 0.6 },
+                Coord { x: -0.6, y: 0.0 },
+                Coord { x: 0.0, y: -0.6 },
+            ]);
+        }
+
+        let axes = cardinal_axes(&coords);
+        assert!(axes.iter().map(|a| a.on_axis).sum::<usize>() >= MIN_CLAMPED_POINTS);
+        assert!(clamped_score(&axes) < 0.3);
 
         let result = check(&coords);
-        assert_eq!(result.result, false);
+        assert!(!result.result);
     }
 
     #[test]
     fn test_goomwave_pattern() {
-        // Need enough unique rim coordinates to not be classified as a box controller
-        // (is_box_controller requires >= 50% of 432 rim coords, with short-game boost)
-        // All coordinates have both |x| and |y| > THRESHOLD (0.08), simulating goomwave clamping
-        let mut coords: Vec<Coord> = Vec::new();
-
-        // Generate 220 unique rim coordinates on the unit circle where both
-        // |x| and |y| are well above THRESHOLD (0.08).
-        // Safe angle range per quadrant: [0.1, 1.47] keeps sin & cos > 0.09
-        // 55 points per quadrant * 4 quadrants = 220
-        for q in 0..4 {
-            let base = q as f64 * std::f64::consts::FRAC_PI_2;
-            for i in 0..55 {
-                let angle = base + 0.1 + (i as f64) * 0.025;
-                let x = angle.cos();
-                let y = angle.sin();
-                coords.push(Coord { x, y });
-            }
-        }
-
-        // Add some cardinal coordinates too (these get skipped by the clamping check)
-        coords.push(Coord { x: 0.0, y: 1.0 });
-        coords.push(Coord { x: 1.0, y: 0.0 });
-        coords.push(Coord { x: 0.0, y: -1.0 });
-        coords.push(Coord { x: -1.0, y: 0.0 });
+        // Snap any coordinate within 0.08 of a cardinal onto it like GoomWave does
+        let mut coords = sweep(0.08);
 
         let result = check(&coords);
-        assert_eq!(result.result, true);
+        assert!(result.result);
+        assert!(!result.details[0].evidence.is_empty());
+
+        // A few stray near-axis inputs don't hide the clamping
+        coords.extend([Coord { x: 0.03, y: 0.6 }, Coord { x: 0.6, y: -0.05 }]);
+        assert!(check(&coords).result);
     }
 }
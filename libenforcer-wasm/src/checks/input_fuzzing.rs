@@ -1,9 +1,18 @@
-use crate::types::{CheckResult, Coord, DeltaCounts, FuzzAnalysis, Violation};
+use crate::types::{CheckResult, Coord, DeltaCounts, FrameUse, FuzzAnalysis, StickStream, Violation};
 use crate::utils::{float_equals, is_equal_coord};
 use std::collections::HashMap;
 
+/// Main stick stream this check reads
+pub const STICK_STREAM: StickStream = StickStream::Processed;
+
+/// Frames this check reads
+pub const FRAMES: FrameUse = FrameUse::Present;
+
 /// One raw coordinate unit in normalized space (1/80)
-const UNIT: f64 = 1.0 / 80.0;
+pub(crate) const UNIT: f64 = 1.0 / 80.0;
+
+/// One raw analog trigger unit (1/140)
+pub const TRIGGER_UNIT: f64 = 1.0 / 140.0;
 
 /// Magnitude threshold above which a NonCardinal coordinate is considered "on the rim".
 /// At raw magnitude ≥ 78, diagonal ±1 fuzz offsets can push past 80 and get absorbed
@@ -14,7 +23,7 @@ const RIM_MAGNITUDE_THRESHOLD: f64 = 0.975; // 78/80
 // H_fuzz:   P(δ=0) = 0.50, P(δ=±1) = 0.25
 // H_nofuzz: P(δ=0) = 0.95, P(δ=±1) = 0.025
 const LLR_DELTA_ZERO: f64 = -0.6418538; // ln(0.50 / 0.95)
-const LLR_DELTA_ONE: f64 = 2.3025851;   // ln(0.25 / 0.025)
+const LLR_DELTA_ONE: f64 = std::f64::consts::LN_10; // ln(0.25 / 0.025)
 
 /// Minimum cumulative evidence (in nats) before declaring a controller unfuzzed.
 /// Uses a Sequential Probability Ratio Test (SPRT) approach: the total log-likelihood
@@ -69,6 +78,47 @@ fn classify_coord(coord: &Coord) -> CoordClass {
     }
 }
 
+/// An analog input the fuzzing engine can analyze
+#[derive(Debug, Clone, Copy)]
+pub enum InputChannel<'a> {
+    /// 2D stick normalized to ±1.0 in steps of 1/80 (main stick, C-stick)
+    Stick(&'a [Coord]),
+    /// 1D axis from 0.0 to 1.0 in steps of `unit` (e.g. an analog trigger).
+    /// 0.0 and a full press are exempt from fuzzing, like the origin and cardinals.
+    Axis { values: &'a [f64], unit: f64 },
+}
+
+/// Resolution and shape of a channel's values, used to key and classify them
+#[derive(Debug, Clone, Copy)]
+struct ChannelSpec {
+    unit: f64,
+    two_d: bool,
+}
+
+impl ChannelSpec {
+    fn key(&self, coord: &Coord) -> (i32, i32) {
+        ((coord.x / self.unit).round() as i32, (coord.y / self.unit).round() as i32)
+    }
+
+    fn coord(&self, key: (i32, i32)) -> Coord {
+        Coord::new(key.0 as f64 * self.unit, key.1 as f64 * self.unit)
+    }
+
+    fn classify(&self, key: (i32, i32)) -> CoordClass {
+        if self.two_d {
+            return classify_coord(&self.coord(key));
+        }
+        let full = (1.0 / self.unit).round() as i32;
+        match key.0 {
+            0 => CoordClass::Origin,
+            k if k.abs() >= full => CoordClass::Cardinal,
+            _ => CoordClass::Deadzone,
+        }
+    }
+}
+
+const STICK: ChannelSpec = ChannelSpec { unit: UNIT, two_d: true };
+
 /// A hold is a sequence of 2+ identical consecutive frames (one targeting event)
 pub struct Hold {
     pub coord: Coord,
@@ -102,7 +152,7 @@ pub fn identify_holds(coords: &[Coord]) -> Vec<Hold> {
 }
 
 /// Key for hashing coordinates using integer units (multiples of 1/80)
-fn coord_key(coord: &Coord) -> (i32, i32) {
+pub(crate) fn coord_key(coord: &Coord) -> (i32, i32) {
     ((coord.x / UNIT).round() as i32, (coord.y / UNIT).round() as i32)
 }
 
@@ -149,11 +199,11 @@ fn neighbor_offsets_for(_coord: &Coord, class: &CoordClass) -> Vec<(i32, i32)> {
 /// 4. Produce events only for unambiguous assignments (skip contested keys)
 ///
 /// Returns only events for non-cardinal, non-origin coordinates (fuzzable targets).
-fn cluster_and_compute_deltas(holds: &[Hold]) -> Vec<FuzzEvent> {
+fn cluster_and_compute_deltas(holds: &[Hold], spec: &ChannelSpec) -> Vec<FuzzEvent> {
     // Group holds by integer key → count
     let mut key_counts: HashMap<(i32, i32), usize> = HashMap::new();
     for hold in holds {
-        let key = coord_key(&hold.coord);
+        let key = spec.key(&hold.coord);
         *key_counts.entry(key).or_insert(0) += 1;
     }
 
@@ -162,8 +212,8 @@ fn cluster_and_compute_deltas(holds: &[Hold]) -> Vec<FuzzEvent> {
     let mut target_keys: Vec<(i32, i32)> = Vec::new();
 
     for &key in key_counts.keys() {
-        let coord = Coord::new(key.0 as f64 * UNIT, key.1 as f64 * UNIT);
-        let class = classify_coord(&coord);
+        let coord = spec.coord(key);
+        let class = spec.classify(key);
 
         match class {
             CoordClass::Cardinal | CoordClass::Origin | CoordClass::Rim => continue,
@@ -175,7 +225,7 @@ fn cluster_and_compute_deltas(holds: &[Hold]) -> Vec<FuzzEvent> {
 
         let is_target = offsets.iter().all(|&(ox, oy)| {
             let nkey = (key.0 + ox, key.1 + oy);
-            key_counts.get(&nkey).map_or(true, |&nc| nc <= my_count)
+            key_counts.get(&nkey).is_none_or(|&nc| nc <= my_count)
         });
 
         if is_target {
@@ -188,8 +238,8 @@ fn cluster_and_compute_deltas(holds: &[Hold]) -> Vec<FuzzEvent> {
     let mut key_claimants: HashMap<(i32, i32), Vec<(i32, i32)>> = HashMap::new();
 
     for &tkey in &target_keys {
-        let coord = Coord::new(tkey.0 as f64 * UNIT, tkey.1 as f64 * UNIT);
-        let class = classify_coord(&coord);
+        let coord = spec.coord(tkey);
+        let class = spec.classify(tkey);
         let offsets = neighbor_offsets_for(&coord, &class);
 
         // The target itself
@@ -219,7 +269,7 @@ fn cluster_and_compute_deltas(holds: &[Hold]) -> Vec<FuzzEvent> {
     // --- Pass 3: Produce events only for unambiguous keys ---
     let mut events = Vec::new();
     for hold in holds {
-        let key = coord_key(&hold.coord);
+        let key = spec.key(&hold.coord);
         let claimants = match key_claimants.get(&key) {
             Some(c) => c,
             None => continue, // not claimed by any target
@@ -237,11 +287,8 @@ fn cluster_and_compute_deltas(holds: &[Hold]) -> Vec<FuzzEvent> {
         if target_cluster_size.get(&target_key).copied().unwrap_or(0) < 2 {
             continue;
         }
-        let target_coord = Coord::new(
-            target_key.0 as f64 * UNIT,
-            target_key.1 as f64 * UNIT,
-        );
-        let class = classify_coord(&target_coord);
+        let target_coord = spec.coord(target_key);
+        let class = spec.classify(target_key);
 
         let dx = key.0 - target_key.0;
         let dy = key.1 - target_key.1;
@@ -366,7 +413,7 @@ fn chi_sq_survival_df2(x: f64) -> f64 {
 
 /// Build human-readable violation entries from fuzz events.
 /// Produces an overall summary violation plus per-target breakdowns for suspicious targets.
-fn build_violations(events: &[FuzzEvent], llr_score: f64) -> Vec<Violation> {
+fn build_violations(events: &[FuzzEvent], llr_score: f64, spec: &ChannelSpec) -> Vec<Violation> {
     let mut violations = Vec::new();
 
     // Group events by target_key
@@ -396,7 +443,7 @@ fn build_violations(events: &[FuzzEvent], llr_score: f64) -> Vec<Violation> {
     });
 
     for (tkey, tevents) in &target_stats {
-        let target_coord = Coord::new(tkey.0 as f64 * UNIT, tkey.1 as f64 * UNIT);
+        let target_coord = spec.coord(*tkey);
         let n = tevents.len();
         if n == 0 {
             continue;
@@ -493,8 +540,19 @@ fn pct(count: usize, total: usize) -> f64 {
 /// Perform full statistical analysis of input fuzzing compliance.
 /// Returns a FuzzAnalysis with LLR score, chi-squared p-values, and delta distributions.
 pub fn analyze(coords: &[Coord]) -> FuzzAnalysis {
-    let holds = identify_holds(coords);
-    let events = cluster_and_compute_deltas(&holds);
+    analyze_channel(InputChannel::Stick(coords))
+}
+
+/// Fuzzing analysis of any analog channel. A 1D axis only reports X-axis deltas.
+pub fn analyze_channel(channel: InputChannel) -> FuzzAnalysis {
+    let (holds, spec) = match channel {
+        InputChannel::Stick(coords) => (identify_holds(coords), STICK),
+        InputChannel::Axis { values, unit } => {
+            let coords: Vec<Coord> = values.iter().map(|&v| Coord::new(v, 0.0)).collect();
+            (identify_holds(&coords), ChannelSpec { unit, two_d: false })
+        }
+    };
+    let events = cluster_and_compute_deltas(&holds, &spec);
     let (x_counts, y_counts) = accumulate_deltas(&events);
     let total_fuzz_events = events.len();
 
@@ -513,7 +571,7 @@ pub fn analyze(coords: &[Coord]) -> FuzzAnalysis {
 
     let (pass, violations) = if total_score < -FAIL_THRESHOLD_NATS {
         // Strong cumulative evidence of no fuzzing
-        (false, build_violations(&events, llr_score))
+        (false, build_violations(&events, llr_score, &spec))
     } else {
         // Insufficient evidence or evidence of fuzzing — pass
         (true, vec![])
@@ -545,6 +603,7 @@ pub fn check(coords: &[Coord]) -> CheckResult {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::make_targeting_sequence;
 
     #[test]
     fn test_classify_coordinate() {
@@ -581,22 +640,6 @@ mod tests {
         assert_eq!(holds[1].start_frame, 4);
     }
 
-    /// Helper: create a coordinate sequence simulating repeated targeting events.
-    /// Each event: neutral hold → travel frame → output hold
-    fn make_targeting_sequence(outputs: &[Coord]) -> Vec<Coord> {
-        let mut coords = Vec::new();
-        for &output in outputs {
-            coords.push(Coord::new(0.0, 0.0));
-            coords.push(Coord::new(0.0, 0.0));
-            coords.push(Coord::new(0.3, 0.3)); // travel
-            coords.push(output);
-            coords.push(output);
-        }
-        coords.push(Coord::new(0.0, 0.0));
-        coords.push(Coord::new(0.0, 0.0));
-        coords
-    }
-
     #[test]
     fn test_llr_positive_for_proper_fuzzing() {
         // Perfect 50/25/25 distribution
@@ -660,7 +703,7 @@ mod tests {
 
         let coords = make_targeting_sequence(&outputs);
         let holds = identify_holds(&coords);
-        let events = cluster_and_compute_deltas(&holds);
+        let events = cluster_and_compute_deltas(&holds, &STICK);
         let (x_counts, _y_counts) = accumulate_deltas(&events);
 
         let llr = compute_llr(&x_counts, &[0, 0, 0]);
@@ -674,7 +717,7 @@ mod tests {
         let outputs: Vec<Coord> = (0..30).map(|_| target).collect();
         let coords = make_targeting_sequence(&outputs);
         let holds = identify_holds(&coords);
-        let events = cluster_and_compute_deltas(&holds);
+        let events = cluster_and_compute_deltas(&holds, &STICK);
         let (x_counts, y_counts) = accumulate_deltas(&events);
 
         let llr = compute_llr(&x_counts, &y_counts);
@@ -687,7 +730,7 @@ mod tests {
         let outputs: Vec<Coord> = (0..50).map(|_| cardinal).collect();
         let coords = make_targeting_sequence(&outputs);
         let holds = identify_holds(&coords);
-        let events = cluster_and_compute_deltas(&holds);
+        let events = cluster_and_compute_deltas(&holds, &STICK);
 
         // Cardinals should produce no fuzz events
         assert_eq!(events.len(), 0, "Cardinals should not produce fuzz events");
@@ -707,7 +750,7 @@ mod tests {
 
         let coords = make_targeting_sequence(&outputs);
         let holds = identify_holds(&coords);
-        let events = cluster_and_compute_deltas(&holds);
+        let events = cluster_and_compute_deltas(&holds, &STICK);
         let (x_counts, y_counts) = accumulate_deltas(&events);
 
         // X should have fuzz data, Y should be empty (deadzone)
@@ -747,7 +790,7 @@ mod tests {
 
         let coords = make_targeting_sequence(&outputs);
         let holds = identify_holds(&coords);
-        let events = cluster_and_compute_deltas(&holds);
+        let events = cluster_and_compute_deltas(&holds, &STICK);
 
         // Both target_a and target_b are within each other's fuzz zone,
         // so both should be contested and produce no events.
@@ -770,7 +813,7 @@ mod tests {
 
         let coords = make_targeting_sequence(&outputs);
         let holds = identify_holds(&coords);
-        let events = cluster_and_compute_deltas(&holds);
+        let events = cluster_and_compute_deltas(&holds, &STICK);
 
         // Both targets are well separated, no overlap — should get events from both
         assert_eq!(
@@ -816,7 +859,7 @@ mod tests {
         let outputs: Vec<Coord> = (0..30).map(|_| rim_coord).collect();
         let coords = make_targeting_sequence(&outputs);
         let holds = identify_holds(&coords);
-        let events = cluster_and_compute_deltas(&holds);
+        let events = cluster_and_compute_deltas(&holds, &STICK);
         assert_eq!(events.len(), 0, "Rim coordinates should produce no fuzz events");
     }
 
@@ -842,4 +885,36 @@ mod tests {
         let analysis = analyze(&coords);
         assert!(!analysis.pass, "Unfuzzed non-rim data should still fail");
     }
+
+    /// Release → lightshield hold, for each trigger output
+    fn make_trigger_sequence(outputs: &[f64]) -> Vec<f64> {
+        outputs.iter().flat_map(|&v| [0.0, 0.0, v, v]).collect()
+    }
+
+    #[test]
+    fn test_unfuzzed_trigger_fails() {
+        let values = make_trigger_sequence(&[49.0 * TRIGGER_UNIT; 30]);
+        let analysis = analyze_channel(InputChannel::Axis { values: &values, unit: TRIGGER_UNIT });
+        assert!(!analysis.pass);
+        assert_eq!(analysis.observed_y, [0, 0, 0], "A 1D axis only has X deltas");
+    }
+
+    #[test]
+    fn test_fuzzed_trigger_passes() {
+        let outputs: Vec<f64> = (0..40)
+            .map(|i| (49 + [0, 1, 0, -1][i % 4]) as f64 * TRIGGER_UNIT)
+            .collect();
+        let values = make_trigger_sequence(&outputs);
+        let analysis = analyze_channel(InputChannel::Axis { values: &values, unit: TRIGGER_UNIT });
+        assert!(analysis.pass);
+        assert_eq!(analysis.total_fuzz_events, 40);
+    }
+
+    #[test]
+    fn test_full_trigger_press_is_exempt() {
+        let values = make_trigger_sequence(&[1.0; 30]);
+        let analysis = analyze_channel(InputChannel::Axis { values: &values, unit: TRIGGER_UNIT });
+        assert!(analysis.pass);
+        assert_eq!(analysis.total_fuzz_events, 0);
+    }
 }
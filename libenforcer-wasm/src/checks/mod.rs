@@ -5,56 +5,413 @@ pub mod crouch_uptilt;
 pub mod sdi;
 pub mod goomwave;
 pub mod input_fuzzing;
+pub mod socd;
+pub mod coord_inventory;
+pub mod firmware;
+pub mod analog_notches;
+pub mod snapping;
+pub mod snapback;
+pub mod remapping;
+pub mod transitions;
+pub mod cstick;
 
 use crate::parser::PlayerGameData;
-use crate::types::{ControllerType, PlayerAnalysis};
+use crate::ruleset::Ruleset;
+use crate::checks::input_fuzzing::InputChannel;
+use crate::types::{
+    CheckFidelity, CheckId, CheckQuality, ControllerType, DataQuality, FrameUse, PlayerAnalysis,
+    StickStream,
+};
 use crate::utils;
 
+/// How a check depends on hitlag data (recorded from Slippi v3.8)
+#[derive(Clone, Copy, PartialEq)]
+enum HitlagUse {
+    Unused,
+    /// Windows the check to hitlag; without it every frame is checked
+    Windowed,
+    /// The check can't run without it
+    Required,
+}
+
+/// Main stick stream a check decides on and how it uses hitlag data.
+/// The match is exhaustive, so every check has quality inputs.
+fn check_inputs(check: CheckId) -> (Option<StickStream>, HitlagUse) {
+    match check {
+        CheckId::TravelTime => (Some(travel_time::STICK_STREAM), HitlagUse::Unused),
+        CheckId::DisallowedCstick
+        | CheckId::Cstick
+        | CheckId::CstickFuzzing
+        | CheckId::LTriggerFuzzing
+        | CheckId::RTriggerFuzzing => (None, HitlagUse::Unused),
+        // Decided on action state sequences; coordinates are only evidence
+        // and exemptions, so approximated sticks don't degrade them
+        CheckId::CrouchUptilt | CheckId::Transitions => (None, HitlagUse::Unused),
+        CheckId::Sdi => (Some(sdi::STICK_STREAM), HitlagUse::Windowed),
+        CheckId::SdiDistance => (Some(sdi::STICK_STREAM), HitlagUse::Required),
+        CheckId::InputFuzzing => (Some(input_fuzzing::STICK_STREAM), HitlagUse::Unused),
+        CheckId::Socd => (Some(socd::STICK_STREAM), HitlagUse::Unused),
+        CheckId::CoordInventory => (Some(coord_inventory::STICK_STREAM), HitlagUse::Unused),
+        CheckId::Goomwave => (Some(goomwave::STICK_STREAM), HitlagUse::Unused),
+        CheckId::UptiltRounding => (Some(uptilt_rounding::STICK_STREAM), HitlagUse::Unused),
+        CheckId::AnalogCalibration => (Some(analog_notches::STICK_STREAM), HitlagUse::Unused),
+        CheckId::Snapping => (Some(snapping::STICK_STREAM), HitlagUse::Unused),
+        CheckId::Snapback => (Some(snapback::STICK_STREAM), HitlagUse::Unused),
+        CheckId::Remapping => (Some(remapping::STICK_STREAM), HitlagUse::Unused),
+    }
+}
+
+/// Checks reported for box controllers
+const BOX_CHECKS: [CheckId; 13] = [
+    CheckId::TravelTime,
+    CheckId::DisallowedCstick,
+    CheckId::Cstick,
+    CheckId::CrouchUptilt,
+    CheckId::Sdi,
+    CheckId::SdiDistance,
+    CheckId::InputFuzzing,
+    CheckId::CstickFuzzing,
+    CheckId::LTriggerFuzzing,
+    CheckId::RTriggerFuzzing,
+    CheckId::Socd,
+    CheckId::CoordInventory,
+    CheckId::Transitions,
+];
+
+/// Checks reported for analog controllers
+const ANALOG_CHECKS: [CheckId; 7] = [
+    CheckId::Goomwave,
+    CheckId::UptiltRounding,
+    CheckId::AnalogCalibration,
+    CheckId::Snapping,
+    CheckId::Snapback,
+    CheckId::Remapping,
+    CheckId::Transitions,
+];
+
+/// Fidelity of one check given the streams and hitlag data it reads
+fn check_quality(data: &PlayerGameData, check: CheckId) -> CheckQuality {
+    let (stream, hitlag) = check_inputs(check);
+    let mut fidelity = CheckFidelity::Full;
+    let mut reasons = Vec::new();
+
+    if let Some(stream) = stream.filter(|&s| data.is_approximated(s)) {
+        fidelity = CheckFidelity::Degraded;
+        let axes = match data.approximated_axes(stream) {
+            (true, true) => "X and Y",
+            (true, false) => "X",
+            _ => "Y",
+        };
+        reasons.push(format!(
+            "{:?} main stick {} values approximated from engine values (raw_analog_x needs Slippi v1.2, raw_analog_y v3.15)",
+            stream, axes
+        ));
+    }
+    if data.hitlag.is_none() {
+        match hitlag {
+            HitlagUse::Unused => {}
+            HitlagUse::Windowed => {
+                fidelity = CheckFidelity::Degraded;
+                reasons.push("no hitlag data (needs Slippi v3.8), every frame checked".to_string());
+            }
+            HitlagUse::Required => {
+                fidelity = CheckFidelity::Skipped;
+                reasons.push("no hitlag data (needs Slippi v3.8)".to_string());
+            }
+        }
+    }
+
+    CheckQuality {
+        check,
+        fidelity,
+        reasons,
+    }
+}
+
+fn data_quality(data: &PlayerGameData, checks: &[CheckId]) -> DataQuality {
+    DataQuality {
+        slippi_version: data.slippi_version.clone(),
+        missing_fields: data.missing_fields.clone(),
+        rollback_frames: data.rollback_frames,
+        approximated_streams: [StickStream::Raw, StickStream::Engine, StickStream::Processed]
+            .into_iter()
+            .filter(|&s| data.is_approximated(s))
+            .collect(),
+        checks: checks.iter().map(|&check| check_quality(data, check)).collect(),
+    }
+}
+
+/// Analyze a player's inputs against the default ruleset.
+pub fn analyze_player(data: &PlayerGameData) -> PlayerAnalysis {
+    analyze_player_with_ruleset(data, &Ruleset::default())
+}
+
 /// Analyze a player's inputs: detect controller type, run applicable checks.
 /// `is_box_controller` is computed once here and not repeated in each check.
-pub fn analyze_player(data: &PlayerGameData) -> PlayerAnalysis {
-    let is_box = utils::is_box_controller(&data.main_coords);
+pub fn analyze_player_with_ruleset(data: &PlayerGameData, ruleset: &Ruleset) -> PlayerAnalysis {
+    let is_box =
+        utils::is_box_controller(&data.stick_frames(StickStream::Processed, FrameUse::Present));
 
     if is_box {
-        let travel_time = travel_time::check(&data.main_coords);
-        let disallowed_cstick = disallowed_analog::check(&data.c_coords);
-        let crouch_uptilt = crouch_uptilt::check(&data.main_coords, &data.action_states);
-        let sdi = sdi::check(&data.main_coords);
-        let input_fuzzing = input_fuzzing::analyze(&data.main_coords);
-
-        let is_legal = !travel_time.result
-            && !disallowed_cstick.result
-            && !crouch_uptilt.result
-            && !sdi.result
-            && input_fuzzing.pass;
+        let travel_time =
+            travel_time::check(&data.stick_frames(travel_time::STICK_STREAM, travel_time::FRAMES));
+        let disallowed_cstick =
+            disallowed_analog::check(&data.frames(&data.c_coords, disallowed_analog::FRAMES));
+        let cstick = cstick::analyze(
+            &data.frames(&data.c_coords, cstick::FRAMES),
+            &data.frame_numbers(cstick::FRAMES),
+            ruleset,
+        );
+        let transitions = transitions::analyze(data, ControllerType::Box, ruleset);
+        let crouch_uptilt = crouch_uptilt::from_transitions(&transitions);
+        let sdi_incidents = sdi::player_incidents(data, ruleset);
+        let sdi = if ruleset.sdi_raw_violations {
+            sdi::check_player(data, ruleset)
+        } else {
+            sdi::check_incidents(&sdi_incidents, &data.stick_frames(sdi::STICK_STREAM, sdi::FRAMES))
+        };
+        let sdi_hits = sdi::player_hits(data, ruleset);
+        let sdi_distance = sdi_hits.as_deref().map(sdi::check_distance);
+        let input_fuzzing = input_fuzzing::analyze(
+            &data.stick_frames(input_fuzzing::STICK_STREAM, input_fuzzing::FRAMES),
+        );
+        let cstick_fuzzing = input_fuzzing::analyze_channel(InputChannel::Stick(
+            &data.frames(&data.c_coords, input_fuzzing::FRAMES),
+        ));
+        let l_trigger_fuzzing = input_fuzzing::analyze_channel(InputChannel::Axis {
+            values: &data.frames(&data.l_trigger, input_fuzzing::FRAMES),
+            unit: input_fuzzing::TRIGGER_UNIT,
+        });
+        let r_trigger_fuzzing = input_fuzzing::analyze_channel(InputChannel::Axis {
+            values: &data.frames(&data.r_trigger, input_fuzzing::FRAMES),
+            unit: input_fuzzing::TRIGGER_UNIT,
+        });
+        let socd = socd::analyze(&data.stick_frames(socd::STICK_STREAM, socd::FRAMES), ruleset);
+        let coord_inventory = coord_inventory::analyze(
+            &data.stick_frames(coord_inventory::STICK_STREAM, coord_inventory::FRAMES),
+            &data.frame_numbers(coord_inventory::FRAMES),
+            ruleset,
+        );
+        let firmware = firmware::fingerprint(&coord_inventory.notches, &input_fuzzing);
+
+        let data_quality = data_quality(data, &BOX_CHECKS);
+        let fails = |check: CheckId, failed: bool| {
+            failed && check_quality(data, check).fidelity.can_fail(ruleset.fail_on_degraded_data)
+        };
+
+        // disallowed_cstick stays informational: cstick applies the ruleset's
+        // forbidden values on both axes, so it already fails those outputs
+        let is_legal = !fails(CheckId::TravelTime, travel_time.result)
+            && !fails(CheckId::Cstick, !cstick.pass)
+            && !fails(CheckId::Sdi, sdi.result)
+            && (!ruleset.require_sdi_distance
+                || !fails(CheckId::SdiDistance, sdi_distance.as_ref().is_some_and(|d| d.result)))
+            && !fails(CheckId::InputFuzzing, !input_fuzzing.pass)
+            && !fails(CheckId::CstickFuzzing, !cstick_fuzzing.pass)
+            && (!ruleset.require_trigger_fuzzing
+                || (!fails(CheckId::LTriggerFuzzing, !l_trigger_fuzzing.pass)
+                    && !fails(CheckId::RTriggerFuzzing, !r_trigger_fuzzing.pass)))
+            && (!ruleset.require_socd_mode || !fails(CheckId::Socd, !socd.pass))
+            && !fails(CheckId::CoordInventory, !coord_inventory.pass)
+            && !fails(CheckId::Transitions, !transitions.pass);
 
         PlayerAnalysis {
             controller_type: ControllerType::Box,
+            team: data.team,
             is_legal,
             travel_time: Some(travel_time),
             disallowed_cstick: Some(disallowed_cstick),
+            cstick: Some(cstick),
             crouch_uptilt: Some(crouch_uptilt),
             sdi: Some(sdi),
+            sdi_incidents: Some(sdi_incidents),
+            sdi_hits,
+            sdi_distance,
             input_fuzzing: Some(input_fuzzing),
+            cstick_fuzzing: Some(cstick_fuzzing),
+            l_trigger_fuzzing: Some(l_trigger_fuzzing),
+            r_trigger_fuzzing: Some(r_trigger_fuzzing),
+            socd: Some(socd),
+            coord_inventory: Some(coord_inventory),
+            firmware: Some(firmware),
             goomwave: None,
             uptilt_rounding: None,
+            analog_calibration: None,
+            snapping: None,
+            snapback: None,
+            remapping: None,
+            transitions,
+            data_quality,
         }
     } else {
-        let goomwave = goomwave::check(&data.main_coords);
-        let uptilt_rounding = uptilt_rounding::check(&data.main_coords);
+        let goomwave =
+            goomwave::check_raw(&data.stick_frames(goomwave::STICK_STREAM, goomwave::FRAMES));
+        let uptilt_rounding = uptilt_rounding::check(
+            &data.stick_frames(uptilt_rounding::STICK_STREAM, uptilt_rounding::FRAMES),
+        );
+        let analog_calibration = analog_notches::analyze(
+            &data.stick_frames(analog_notches::STICK_STREAM, analog_notches::FRAMES),
+            ruleset,
+        );
+        let snapping =
+            snapping::analyze_raw(&data.stick_frames(snapping::STICK_STREAM, snapping::FRAMES), ruleset);
+        let snapback =
+            snapback::analyze(&data.stick_frames(snapback::STICK_STREAM, snapback::FRAMES), ruleset);
+        let remapping =
+            remapping::analyze(&data.stick_frames(remapping::STICK_STREAM, remapping::FRAMES), ruleset);
+        let transitions = transitions::analyze(data, ControllerType::Analog, ruleset);
+
+        let data_quality = data_quality(data, &ANALOG_CHECKS);
+        let fails = |check: CheckId, failed: bool| {
+            failed && check_quality(data, check).fidelity.can_fail(ruleset.fail_on_degraded_data)
+        };
 
-        let is_legal = !goomwave.result && !uptilt_rounding.result;
+        let is_legal = !fails(CheckId::Goomwave, goomwave.result)
+            && !fails(CheckId::UptiltRounding, uptilt_rounding.result)
+            && !fails(CheckId::AnalogCalibration, !analog_calibration.pass)
+            && !fails(CheckId::Snapping, !snapping.pass)
+            && !fails(CheckId::Snapback, !snapback.pass)
+            && !fails(CheckId::Remapping, !remapping.pass)
+            && !fails(CheckId::Transitions, !transitions.pass);
 
         PlayerAnalysis {
             controller_type: ControllerType::Analog,
+            team: data.team,
             is_legal,
             travel_time: None,
             disallowed_cstick: None,
+            cstick: None,
             crouch_uptilt: None,
             sdi: None,
+            sdi_incidents: None,
+            sdi_hits: None,
+            sdi_distance: None,
             input_fuzzing: None,
+            cstick_fuzzing: None,
+            l_trigger_fuzzing: None,
+            r_trigger_fuzzing: None,
+            socd: None,
+            coord_inventory: None,
+            firmware: None,
             goomwave: Some(goomwave),
             uptilt_rounding: Some(uptilt_rounding),
+            analog_calibration: Some(analog_calibration),
+            snapping: Some(snapping),
+            snapback: Some(snapback),
+            remapping: Some(remapping),
+            transitions,
+            data_quality,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::action_states;
+    use crate::test_utils::player_data;
+    use crate::types::Coord;
+
+    /// Box-like inputs snapping between cardinals with no travel coordinates,
+    /// recorded by a replay without raw stick Y or hitlag data
+    fn degraded_box_data() -> PlayerGameData {
+        let targets = [
+            Coord::new(0.0, 0.0),
+            Coord::new(1.0, 0.0),
+            Coord::new(0.0, 0.0),
+            Coord::new(-1.0, 0.0),
+        ];
+        let mut data = player_data((0..400).map(|i| targets[(i / 5) % 4]).collect());
+        data.raw_y_approximated = true;
+        data.hitlag = None;
+        data.slippi_version = "3.7.0".to_string();
+        data.missing_fields = vec!["raw_analog_y".to_string(), "hitlag".to_string()];
+        data
+    }
+
+    #[test]
+    fn test_degraded_checks_do_not_fail() {
+        let data = degraded_box_data();
+        let analysis = analyze_player(&data);
+        let quality = &analysis.data_quality;
+
+        assert_eq!(quality.slippi_version, "3.7.0");
+        assert_eq!(quality.approximated_streams, vec![StickStream::Raw, StickStream::Processed]);
+        let fidelity = |check: CheckId| quality.checks.iter().find(|q| q.check == check).unwrap().fidelity;
+        assert_eq!(fidelity(CheckId::TravelTime), CheckFidelity::Degraded);
+        assert_eq!(fidelity(CheckId::SdiDistance), CheckFidelity::Skipped);
+        assert_eq!(fidelity(CheckId::Cstick), CheckFidelity::Full);
+
+        assert!(analysis.travel_time.as_ref().unwrap().result);
+        assert!(analysis.is_legal);
+
+        let ruleset = Ruleset {
+            fail_on_degraded_data: true,
+            ..Ruleset::default()
+        };
+        assert!(!analyze_player_with_ruleset(&data, &ruleset).is_legal);
+    }
+
+    #[test]
+    fn test_crouch_uptilt_fails_without_raw_stick_y() {
+        // Transition rules read action states, which every replay records
+        let mut data = degraded_box_data();
+        data.action_states[200] = action_states::SQUAT_WAIT;
+        data.action_states[201] = action_states::ATTACK_HI3;
+
+        let analysis = analyze_player(&data);
+        let fidelity = |check: CheckId| {
+            analysis.data_quality.checks.iter().find(|q| q.check == check).unwrap().fidelity
+        };
+        assert_eq!(fidelity(CheckId::Transitions), CheckFidelity::Full);
+        assert_eq!(fidelity(CheckId::CrouchUptilt), CheckFidelity::Full);
+        assert!(analysis.crouch_uptilt.as_ref().unwrap().result);
+        assert!(!analysis.is_legal);
+    }
+
+    #[test]
+    fn test_sdi_distance_only_fails_when_required() {
+        // Fuzzed box inputs with travel frames, on a replay recording everything
+        let fuzz = [0.0, input_fuzzing::UNIT, 0.0, -input_fuzzing::UNIT];
+        let mut data = player_data(
+            (0..400)
+                .map(|i| match i % 6 {
+                    0 | 1 => Coord::new(0.0, 0.0),
+                    2 => Coord::new(0.3, 0.0),
+                    3 => Coord::new(0.6, 0.0),
+                    _ => Coord::new(1.0, fuzz[(i / 6) % 4]),
+                })
+                .collect(),
+        );
+
+        // Knocked 40 units during a 6 frame hitlag window with the stick at neutral
+        let mut hitlag = vec![0.0; 400];
+        hitlag[200..206].fill(6.0);
+        for i in 200..206 {
+            data.action_states[i] = action_states::DAMAGE_HI1;
+            data.main_coords[i] = Coord::new(0.0, 0.0);
+            data.positions[i] = Coord::new((i - 199) as f64 * 8.0, 0.0);
+        }
+        for i in 206..400 {
+            data.positions[i] = Coord::new(48.0, 0.0);
+        }
+        data.hitlag = Some(hitlag);
+
+        let analysis = analyze_player(&data);
+        assert!(analysis.sdi_distance.as_ref().unwrap().result);
+        assert!(analysis.is_legal);
+
+        let ruleset = Ruleset {
+            require_sdi_distance: true,
+            ..Ruleset::default()
+        };
+        assert!(!analyze_player_with_ruleset(&data, &ruleset).is_legal);
+    }
+
+    #[test]
+    fn test_unreported_checks_can_fail() {
+        let quality = analyze_player(&degraded_box_data()).data_quality;
+        assert!(quality.can_fail(CheckId::Goomwave, false));
+        assert!(!quality.can_fail(CheckId::SdiDistance, true));
+    }
+}
@@ -0,0 +1,256 @@
+use crate::ruleset::Ruleset;
+use crate::types::{
+    Coord, FrameUse, RemapAnalysis, RemapAxis, RemapGap, StickRegion, StickStream, Violation,
+};
+
+/// Main stick stream this check reads
+pub const STICK_STREAM: StickStream = StickStream::Raw;
+
+/// Frames this check reads
+pub const FRAMES: FrameUse = FrameUse::Present;
+
+/// Raw values span -128..=127
+const GRID_SIZE: usize = 256;
+const GRID_OFFSET: i32 = 128;
+
+/// Values this close to neutral are skipped (deadzone handling differs between sticks)
+const MIN_VALUE: i32 = 3;
+
+/// Largest raw value (or magnitude) examined for gaps
+const MAX_VALUE: i32 = 75;
+
+/// Mean count of the two values on either side needed before an empty value counts as a gap
+const MIN_NEIGHBOR_MEAN: f64 = 4.0;
+
+/// Minimum examined values with enough data before a curve is judged
+const MIN_DENSE_VALUES: usize = 40;
+
+/// Most coordinates kept as evidence per forbidden region
+const MAX_EVIDENCE_FRAMES: usize = 20;
+
+/// 2D histogram of raw stick values, counting only frames where the stick moved
+struct Histogram2D {
+    counts: Vec<Vec<usize>>,
+}
+
+impl Histogram2D {
+    fn build(raw_coords: &[Coord]) -> Self {
+        let mut counts = vec![vec![0usize; GRID_SIZE]; GRID_SIZE];
+        for pair in raw_coords.windows(2) {
+            if pair[0].x == pair[1].x && pair[0].y == pair[1].y {
+                continue;
+            }
+            let (x, y) = (pair[1].x.round() as i32, pair[1].y.round() as i32);
+            counts[index(x)][index(y)] += 1;
+        }
+        Histogram2D { counts }
+    }
+
+    /// Samples per raw value on one axis (row or column sums)
+    fn marginal(&self, axis: RemapAxis) -> Vec<usize> {
+        let mut out = vec![0usize; GRID_SIZE];
+        for (xi, column) in self.counts.iter().enumerate() {
+            for (yi, &count) in column.iter().enumerate() {
+                match axis {
+                    RemapAxis::X => out[xi] += count,
+                    RemapAxis::Y => out[yi] += count,
+                    RemapAxis::Magnitude => {
+                        let (x, y) = (xi as i32 - GRID_OFFSET, yi as i32 - GRID_OFFSET);
+                        let magnitude = ((x * x + y * y) as f64).sqrt().round() as i32;
+                        if magnitude < GRID_OFFSET {
+                            out[index(magnitude)] += count;
+                        }
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+fn index(value: i32) -> usize {
+    (value + GRID_OFFSET).clamp(0, GRID_SIZE as i32 - 1) as usize
+}
+
+/// Find values that are never output although both sides of them are well used.
+/// Returns the number of values examined and the merged gap ranges.
+fn find_gaps(counts: &[usize], axis: RemapAxis) -> (usize, Vec<RemapGap>) {
+    let values: Vec<i32> = match axis {
+        RemapAxis::Magnitude => (MIN_VALUE..=MAX_VALUE).collect(),
+        _ => (-MAX_VALUE..=-MIN_VALUE).chain(MIN_VALUE..=MAX_VALUE).collect(),
+    };
+
+    let mut dense = 0;
+    let mut gaps: Vec<RemapGap> = Vec::new();
+    for value in values {
+        let neighbors: usize = [-2, -1, 1, 2].iter().map(|d| counts[index(value + d)]).sum();
+        if (neighbors as f64 / 4.0) < MIN_NEIGHBOR_MEAN {
+            continue;
+        }
+        dense += 1;
+        if counts[index(value)] > 0 {
+            continue;
+        }
+
+        match gaps.last_mut() {
+            Some(gap) if gap.end + 1 == value => gap.end = value,
+            _ => gaps.push(RemapGap {
+                axis,
+                start: value,
+                end: value,
+            }),
+        }
+    }
+
+    (dense, gaps)
+}
+
+/// Frames whose raw output falls in a region
+fn region_frames(raw_coords: &[Coord], region: &StickRegion) -> Vec<usize> {
+    raw_coords
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| region.contains(c.x.round() as i32, c.y.round() as i32))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+fn region_violation(raw_coords: &[Coord], region: &StickRegion, frames: &[usize]) -> Violation {
+    Violation::with_evidence(
+        frames[0] as f64,
+        format!(
+            "Stick output a forbidden region (|x| {}..={}, |y| {}..={}) on {} frames",
+            region.x_min,
+            region.x_max,
+            region.y_min,
+            region.y_max,
+            frames.len(),
+        ),
+        frames.iter().take(MAX_EVIDENCE_FRAMES).map(|&f| raw_coords[f]).collect(),
+    )
+}
+
+/// Detect non-native analog curves (ESS-style remapping) from raw stick values.
+///
+/// A native stick outputs every raw value it passes through. A remapping curve
+/// that stretches part of the range skips values there, leaving gaps in the
+/// distribution along an axis or in magnitude. Outputs inside the ruleset's
+/// forbidden regions fail as well. Takes raw int8-scale stick values.
+pub fn analyze(raw_coords: &[Coord], ruleset: &Ruleset) -> RemapAnalysis {
+    let histogram = Histogram2D::build(raw_coords);
+
+    let mut dense_values = 0;
+    let mut axis_gap_values = 0;
+    let mut gaps = Vec::new();
+    for axis in [RemapAxis::X, RemapAxis::Y, RemapAxis::Magnitude] {
+        let (dense, axis_gaps) = find_gaps(&histogram.marginal(axis), axis);
+        if axis != RemapAxis::Magnitude {
+            dense_values += dense;
+            axis_gap_values += axis_gaps.iter().map(|g| (g.end - g.start + 1) as usize).sum::<usize>();
+        }
+        gaps.extend(axis_gaps);
+    }
+
+    let gap_fraction = if dense_values == 0 {
+        0.0
+    } else {
+        axis_gap_values as f64 / dense_values as f64
+    };
+
+    let mut violations = Vec::new();
+    if dense_values >= MIN_DENSE_VALUES
+        && axis_gap_values >= ruleset.min_remap_gap_values
+        && gap_fraction >= ruleset.min_remap_gap_fraction
+    {
+        violations.push(Violation::new(
+            gap_fraction,
+            format!(
+                "{} of {} stick values are never output ({:.0}%), characteristic of a remapped (non-native) analog curve",
+                axis_gap_values,
+                dense_values,
+                gap_fraction * 100.0,
+            ),
+        ));
+    }
+
+    let mut forbidden_region_frames = 0;
+    for region in &ruleset.forbidden_stick_regions {
+        let frames = region_frames(raw_coords, region);
+        if !frames.is_empty() {
+            forbidden_region_frames += frames.len();
+            violations.push(region_violation(raw_coords, region, &frames));
+        }
+    }
+
+    RemapAnalysis {
+        pass: violations.is_empty(),
+        dense_values,
+        gap_values: axis_gap_values,
+        gap_fraction,
+        gaps,
+        forbidden_region_frames,
+        violations,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sweep every raw value on both axes several times, passed through `curve`
+    fn sweep(curve: impl Fn(f64) -> f64) -> Vec<Coord> {
+        let mut coords = Vec::new();
+        for pass in 0..10 {
+            for v in -80..=80 {
+                let v = curve(v as f64);
+                coords.push(Coord::new(v, (pass % 3) as f64));
+                coords.push(Coord::new((pass % 3) as f64, v));
+            }
+        }
+        coords
+    }
+
+    #[test]
+    fn test_native_curve_has_no_gaps() {
+        let analysis = analyze(&sweep(|v| v), &Ruleset::default());
+        assert!(analysis.pass);
+        assert_eq!(analysis.gap_values, 0);
+        assert!(analysis.dense_values >= MIN_DENSE_VALUES);
+    }
+
+    #[test]
+    fn test_stretched_curve_is_flagged() {
+        // Steepen the curve near neutral, like an ESS-style remap
+        let curve = sweep(|v| (v.signum() * 80.0 * (v.abs() / 80.0).powf(0.6)).round());
+        let analysis = analyze(&curve, &Ruleset::default());
+        assert!(!analysis.pass);
+        assert!(analysis.gap_fraction > Ruleset::default().min_remap_gap_fraction);
+        assert!(analysis.gaps.iter().any(|g| g.axis == RemapAxis::X));
+        assert!(analysis.gaps.iter().any(|g| g.axis == RemapAxis::Y));
+
+        let lenient = Ruleset {
+            min_remap_gap_values: analysis.gap_values + 1,
+            ..Ruleset::default()
+        };
+        assert!(analyze(&curve, &lenient).pass);
+    }
+
+    #[test]
+    fn test_forbidden_region() {
+        let ruleset = Ruleset {
+            forbidden_stick_regions: vec![StickRegion {
+                x_min: 70,
+                x_max: 80,
+                y_min: 0,
+                y_max: 2,
+            }],
+            ..Ruleset::default()
+        };
+        let analysis = analyze(&sweep(|v| v), &ruleset);
+        assert!(!analysis.pass);
+        assert_eq!(analysis.violations.len(), 1);
+        // Both signs of |x| 70..=80, on the y = 0..2 rows of every pass
+        assert_eq!(analysis.forbidden_region_frames, 22 * 10);
+        assert_eq!(analysis.violations[0].evidence.len(), MAX_EVIDENCE_FRAMES);
+    }
+}
@@ -1,8 +1,29 @@
-use crate::types::{CheckResult, Coord, Violation};
+use crate::action_states;
+use crate::parser::PlayerGameData;
+use crate::ruleset::Ruleset;
+use crate::types::{
+    CheckResult, Coord, FrameUse, JoystickRegion, RegionGeometry, SdiHit, SdiIncident, StickStream,
+    Violation,
+};
+use crate::utils;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
+/// Main stick stream this check reads
+pub const STICK_STREAM: StickStream = StickStream::Processed;
+
+/// Frames this check reads
+pub const FRAMES: FrameUse = FrameUse::Aligned;
+
+/// Game units one SDI input moves a character (for a hitbox SDI multiplier of 1.0).
+/// Hitbox multipliers, ASDI, moving platforms and slopes aren't modelled.
+const SDI_DISTANCE: f64 = 6.0;
+
+/// Slack (game units) allowed over the maximum legal displacement before a hit is an outlier
+const DISPLACEMENT_TOLERANCE: f64 = 0.5;
+
 /// SDI regions for directional input classification
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum SDIRegion {
     DZ = 0,    // Deadzone
     NE = 1,    // Northeast diagonal
@@ -32,43 +53,27 @@ const CARDINALS: [SDIRegion; 4] = [
 
 /// Classify a coordinate into an SDI region
 pub fn get_sdi_region(x: f64, y: f64) -> SDIRegion {
-    // Deadzone
-    if x.abs() <= 0.2875 && y.abs() <= 0.2875 {
-        return SDIRegion::DZ;
-    }
-
-    let magnitude = (x.powi(2) + y.powi(2)).sqrt();
-
-    // Diagonals (need both X and Y beyond threshold AND sufficient magnitude)
-    if x >= 0.2875 && y >= 0.2875 && magnitude >= 0.7 {
-        return SDIRegion::NE;
-    }
-    if x >= 0.2875 && y <= -0.2875 && magnitude >= 0.7 {
-        return SDIRegion::SE;
-    }
-    if x <= -0.2875 && y <= -0.2875 && magnitude >= 0.7 {
-        return SDIRegion::SW;
-    }
-    if x <= -0.2875 && y >= 0.2875 && magnitude >= 0.7 {
-        return SDIRegion::NW;
-    }
+    get_sdi_region_with(x, y, &RegionGeometry::SDI)
+}
 
-    // Cardinals (magnitude must be >= 0.7 if we reach here)
-    if y >= 0.7 {
-        return SDIRegion::N;
-    }
-    if x >= 0.7 {
-        return SDIRegion::E;
-    }
-    if y <= -0.7 {
-        return SDIRegion::S;
-    }
-    if x <= -0.7 {
-        return SDIRegion::W;
+/// Classify a coordinate into an SDI region with custom thresholds
+pub fn get_sdi_region_with(x: f64, y: f64, geometry: &RegionGeometry) -> SDIRegion {
+    match utils::classify_region(x, y, geometry) {
+        Some(JoystickRegion::DZ) => SDIRegion::DZ,
+        Some(JoystickRegion::NE) => SDIRegion::NE,
+        Some(JoystickRegion::SE) => SDIRegion::SE,
+        Some(JoystickRegion::SW) => SDIRegion::SW,
+        Some(JoystickRegion::NW) => SDIRegion::NW,
+        Some(JoystickRegion::N) => SDIRegion::N,
+        Some(JoystickRegion::E) => SDIRegion::E,
+        Some(JoystickRegion::S) => SDIRegion::S,
+        Some(JoystickRegion::W) => SDIRegion::W,
+        None => SDIRegion::TILT,
     }
+}
 
-    // Everything else is tilt zone
-    SDIRegion::TILT
+fn regions(coords: &[Coord], geometry: &RegionGeometry) -> Vec<SDIRegion> {
+    coords.iter().map(|c| get_sdi_region_with(c.x, c.y, geometry)).collect()
 }
 
 /// Check if two regions are directly adjacent
@@ -113,13 +118,49 @@ fn count_unique_coordinates(coords: &[Coord]) -> usize {
     unique.len()
 }
 
+/// Frames where the player's stick input can SDI: in hitlag after being hit
+/// (damage states) or after a hit on their shield. The attacker's hitlag is excluded.
+pub fn sdi_frames(hitlag: &[f32], action_states: &[u16]) -> Vec<bool> {
+    hitlag
+        .iter()
+        .zip(action_states)
+        .map(|(&remaining, &state)| {
+            remaining > 0.0
+                && (action_states::is_damaged(state) || state == action_states::GUARD_SET_OFF)
+        })
+        .collect()
+}
+
+/// Whether an input pattern spanning frames `start..=end` overlaps an SDI window
+/// (always true without hitlag data)
+fn in_hitlag(sdi_frames: Option<&[bool]>, start: usize, end: usize) -> bool {
+    sdi_frames.is_none_or(|frames| {
+        frames
+            .get(start..=end.min(frames.len().saturating_sub(1)))
+            .is_some_and(|window| window.contains(&true))
+    })
+}
+
 /// SDI Rule #1: Rapidly tapping the same direction and returning to neutral
 /// faster than once every 5.5 frames triggers 1 SDI and ignores subsequent attempts
 pub fn fails_sdi_rule_one(coords: &[Coord]) -> Vec<Violation> {
+    rule_one(coords, None, &RegionGeometry::SDI)
+}
+
+/// SDI Rule #1, counting only violations whose inputs overlap hitlag
+pub fn fails_sdi_rule_one_in_hitlag(coords: &[Coord], sdi_frames: &[bool]) -> Vec<Violation> {
+    rule_one(coords, Some(sdi_frames), &RegionGeometry::SDI)
+}
+
+fn rule_one(
+    coords: &[Coord],
+    sdi_frames: Option<&[bool]>,
+    geometry: &RegionGeometry,
+) -> Vec<Violation> {
     let mut violations = Vec::new();
 
     // Convert all coords to regions
-    let regions: Vec<SDIRegion> = coords.iter().map(|c| get_sdi_region(c.x, c.y)).collect();
+    let regions = regions(coords, geometry);
 
     for (i, &region) in regions.iter().enumerate() {
         // Look ahead 10 frames when starting from deadzone
@@ -162,7 +203,7 @@ pub fn fails_sdi_rule_one(coords: &[Coord]) -> Vec<Violation> {
             {
                 let current_frame = (i + j) as i32;
 
-                if current_frame <= last_sdi_frame + 4 {
+                if current_frame <= last_sdi_frame + 4 && in_hitlag(sdi_frames, i, i + j) {
                     // This is a hack to be lenient as long as there's travel time
                     if count_unique_coordinates(&coords[i..i + j]) <= 2 {
                         // Two SDI frames were less than 5 frames away from each other!
@@ -193,9 +234,22 @@ pub fn fails_sdi_rule_one(coords: &[Coord]) -> Vec<Violation> {
 /// SDI Rule #2: Rapidly tapping the same diagonal and returning to an adjacent cardinal
 /// faster than once every 5.5 frames
 pub fn fails_sdi_rule_two(coords: &[Coord]) -> Vec<Violation> {
+    rule_two(coords, None, &RegionGeometry::SDI)
+}
+
+/// SDI Rule #2, counting only violations whose inputs overlap hitlag
+pub fn fails_sdi_rule_two_in_hitlag(coords: &[Coord], sdi_frames: &[bool]) -> Vec<Violation> {
+    rule_two(coords, Some(sdi_frames), &RegionGeometry::SDI)
+}
+
+fn rule_two(
+    coords: &[Coord],
+    sdi_frames: Option<&[bool]>,
+    geometry: &RegionGeometry,
+) -> Vec<Violation> {
     let mut violations = Vec::new();
 
-    let regions: Vec<SDIRegion> = coords.iter().map(|c| get_sdi_region(c.x, c.y)).collect();
+    let regions = regions(coords, geometry);
 
     for i in 0..regions.len() {
         let starting_region = regions[i];
@@ -208,6 +262,7 @@ pub fn fails_sdi_rule_two(coords: &[Coord]) -> Vec<Violation> {
         // Look 4 frames ahead
         // Do we alternate between here and an adjacent diagonal twice?
         let mut sdi_count = 0;
+        let mut repeated_in_hitlag = false;
         let mut adjacent_diagonal_region: Option<SDIRegion> = None;
 
         for j in 1..=4 {
@@ -223,17 +278,18 @@ pub fn fails_sdi_rule_two(coords: &[Coord]) -> Vec<Violation> {
             // Have we hit the diagonal SDI? (an adjacent diagonal)
             if is_region_adjacent(starting_region, regions[i + j])
                 && DIAGONALS.contains(&regions[i + j])
+                && (adjacent_diagonal_region.is_none()
+                    || adjacent_diagonal_region == Some(regions[i + j]))
             {
-                if adjacent_diagonal_region.is_none()
-                    || adjacent_diagonal_region == Some(regions[i + j])
-                {
-                    adjacent_diagonal_region = Some(regions[i + j]);
-                    sdi_count += 1;
+                adjacent_diagonal_region = Some(regions[i + j]);
+                sdi_count += 1;
+                if sdi_count >= 2 && in_hitlag(sdi_frames, i, i + j) {
+                    repeated_in_hitlag = true;
                 }
             }
         }
 
-        if sdi_count >= 2 {
+        if repeated_in_hitlag {
             let evidence = coords[i..(i + 5).min(coords.len())].to_vec();
             violations.push(Violation::with_evidence(
                 i as f64,
@@ -248,9 +304,22 @@ pub fn fails_sdi_rule_two(coords: &[Coord]) -> Vec<Violation> {
 
 /// SDI Rule #3: Alternating between adjacent diagonals
 pub fn fails_sdi_rule_three(coords: &[Coord]) -> Vec<Violation> {
+    rule_three(coords, None, &RegionGeometry::SDI)
+}
+
+/// SDI Rule #3, counting only violations whose inputs overlap hitlag
+pub fn fails_sdi_rule_three_in_hitlag(coords: &[Coord], sdi_frames: &[bool]) -> Vec<Violation> {
+    rule_three(coords, Some(sdi_frames), &RegionGeometry::SDI)
+}
+
+fn rule_three(
+    coords: &[Coord],
+    sdi_frames: Option<&[bool]>,
+    geometry: &RegionGeometry,
+) -> Vec<Violation> {
     let mut violations = Vec::new();
 
-    let regions: Vec<SDIRegion> = coords.iter().map(|c| get_sdi_region(c.x, c.y)).collect();
+    let regions = regions(coords, geometry);
 
     for i in 0..regions.len() {
         let current_region = regions[i];
@@ -262,14 +331,15 @@ pub fn fails_sdi_rule_three(coords: &[Coord]) -> Vec<Violation> {
         // Look forward 4 frames to see if it goes to an adjacent diagonal and back
         let mut hit_adjacent = false;
 
-        for j in (i + 1)..=(i + 4).min(regions.len() - 1) {
+        let last = (i + 4).min(regions.len() - 1);
+        for (frame, &region) in regions.iter().enumerate().take(last + 1).skip(i + 1) {
             // Hit the adjacent diagonal
-            if is_diagonal_adjacent(regions[j], current_region) {
+            if is_diagonal_adjacent(region, current_region) {
                 hit_adjacent = true;
             }
 
             // Then returned back
-            if hit_adjacent && regions[j] == current_region {
+            if hit_adjacent && region == current_region && in_hitlag(sdi_frames, i, frame) {
                 let evidence = coords[i..(i + 5).min(coords.len())].to_vec();
                 violations.push(Violation::with_evidence(
                     i as f64,
@@ -285,11 +355,23 @@ pub fn fails_sdi_rule_three(coords: &[Coord]) -> Vec<Violation> {
 
 /// Check for illegal SDI patterns
 pub fn check(coords: &[Coord]) -> CheckResult {
-    let mut all_violations = Vec::new();
+    check_frames(coords, None, &RegionGeometry::SDI)
+}
+
+/// Check for illegal SDI patterns, only counting input patterns that overlap hitlag
+pub fn check_in_hitlag(coords: &[Coord], sdi_frames: &[bool]) -> CheckResult {
+    check_frames(coords, Some(sdi_frames), &RegionGeometry::SDI)
+}
 
-    all_violations.extend(fails_sdi_rule_one(coords));
-    all_violations.extend(fails_sdi_rule_two(coords));
-    all_violations.extend(fails_sdi_rule_three(coords));
+fn check_frames(
+    coords: &[Coord],
+    sdi_frames: Option<&[bool]>,
+    geometry: &RegionGeometry,
+) -> CheckResult {
+    let all_violations: Vec<Violation> = rule_violations(coords, sdi_frames, geometry)
+        .into_iter()
+        .map(|(_, violation)| violation)
+        .collect();
 
     if all_violations.is_empty() {
         CheckResult::pass()
@@ -298,6 +380,308 @@ pub fn check(coords: &[Coord]) -> CheckResult {
     }
 }
 
+/// Violations of all three rules, tagged with the rule number
+fn rule_violations(
+    coords: &[Coord],
+    sdi_frames: Option<&[bool]>,
+    geometry: &RegionGeometry,
+) -> Vec<(u8, Violation)> {
+    let mut all_violations = Vec::new();
+
+    all_violations.extend(rule_one(coords, sdi_frames, geometry).into_iter().map(|v| (1, v)));
+    all_violations.extend(rule_two(coords, sdi_frames, geometry).into_iter().map(|v| (2, v)));
+    all_violations.extend(rule_three(coords, sdi_frames, geometry).into_iter().map(|v| (3, v)));
+
+    all_violations
+}
+
+/// Merge rule violations into incidents. Each violation covers the frames of
+/// its evidence window; windows that overlap, from any rule, are one incident.
+pub fn group_incidents(violations: &[(u8, Violation)]) -> Vec<SdiIncident> {
+    let mut windows: Vec<(i32, i32, u8)> = violations
+        .iter()
+        .map(|(rule, violation)| {
+            let start = violation.metric as i32 - 123; // Frames start at -123
+            let end = start + violation.evidence.len().max(1) as i32 - 1;
+            (start, end, *rule)
+        })
+        .collect();
+    windows.sort_by_key(|&(start, end, _)| (start, end));
+
+    let mut incidents: Vec<SdiIncident> = Vec::new();
+    for (start, end, rule) in windows {
+        match incidents.last_mut() {
+            Some(incident) if start <= incident.end_frame => {
+                incident.end_frame = incident.end_frame.max(end);
+                incident.count += 1;
+                if !incident.rules.contains(&rule) {
+                    incident.rules.push(rule);
+                    incident.rules.sort_unstable();
+                }
+            }
+            _ => incidents.push(SdiIncident {
+                start_frame: start,
+                end_frame: end,
+                rules: vec![rule],
+                count: 1,
+            }),
+        }
+    }
+
+    incidents
+}
+
+fn player_sdi_frames(data: &PlayerGameData) -> Option<Vec<bool>> {
+    data.hitlag
+        .as_ref()
+        .map(|hitlag| sdi_frames(hitlag, &data.action_states))
+}
+
+/// SDI incidents for a player, restricted to hitlag when the replay records it
+pub fn player_incidents(data: &PlayerGameData, ruleset: &Ruleset) -> Vec<SdiIncident> {
+    let frames = player_sdi_frames(data);
+    group_incidents(&rule_violations(
+        data.stick(STICK_STREAM),
+        frames.as_deref(),
+        &ruleset.sdi_regions,
+    ))
+}
+
+/// Report one violation per incident instead of one per rule match.
+/// Evidence is the incident's coordinates, the metric its first frame.
+pub fn check_incidents(incidents: &[SdiIncident], coords: &[Coord]) -> CheckResult {
+    let violations: Vec<Violation> = incidents
+        .iter()
+        .map(|incident| {
+            let rules: Vec<String> = incident.rules.iter().map(|rule| format!("#{}", rule)).collect();
+            let start = (incident.start_frame + 123).max(0) as usize;
+            let end = ((incident.end_frame + 124).max(0) as usize).min(coords.len());
+            Violation::with_evidence(
+                incident.start_frame as f64,
+                format!(
+                    "Failed SDI rule {} ({} violations, frame {} to {})",
+                    rules.join(", "),
+                    incident.count,
+                    incident.start_frame,
+                    incident.end_frame
+                ),
+                coords.get(start..end).unwrap_or_default().to_vec(),
+            )
+        })
+        .collect();
+
+    if violations.is_empty() {
+        CheckResult::pass()
+    } else {
+        CheckResult::fail(violations)
+    }
+}
+
+/// Check a player's SDI, restricted to hitlag when the replay records it
+/// (Slippi v3.8+). Older replays fall back to scanning every frame.
+/// Metrics are game frames, like `check_incidents`.
+pub fn check_player(data: &PlayerGameData, ruleset: &Ruleset) -> CheckResult {
+    let frames = player_sdi_frames(data);
+    let mut result = check_frames(data.stick(STICK_STREAM), frames.as_deref(), &ruleset.sdi_regions);
+    for violation in result.details.iter_mut() {
+        violation.metric -= 123.0; // Frames start at -123
+    }
+    result
+}
+
+fn is_sdi_region(region: SDIRegion) -> bool {
+    CARDINALS.contains(&region) || DIAGONALS.contains(&region)
+}
+
+/// Most SDI displacement a legal controller can gain in a window of hitlag frames.
+/// No SDI is possible on the frame the hit lands, and the stick has to leave an
+/// SDI region and re-enter one between inputs, so at most every other frame counts.
+pub fn max_legal_displacement(hitlag_frames: usize) -> f64 {
+    hitlag_frames.saturating_sub(1).div_ceil(2) as f64 * SDI_DISTANCE
+}
+
+/// Count SDI inputs and measure displacement in each hitlag window.
+/// An SDI input is a frame where the stick enters a new cardinal or diagonal region.
+/// Displacement is the distance the character travelled between hitlag frames
+/// (`positions` are post-frame positions). SDI causes most of it, but ASDI,
+/// moving platforms and slopes move characters too, so outliers are only
+/// worth a closer look.
+pub fn hits(coords: &[Coord], positions: &[Coord], sdi_frames: &[bool]) -> Vec<SdiHit> {
+    hits_with(coords, positions, sdi_frames, &RegionGeometry::SDI)
+}
+
+fn hits_with(
+    coords: &[Coord],
+    positions: &[Coord],
+    sdi_frames: &[bool],
+    geometry: &RegionGeometry,
+) -> Vec<SdiHit> {
+    let regions = regions(coords, geometry);
+    let mut hits: Vec<SdiHit> = Vec::new();
+    let mut in_window = false;
+
+    for (i, &is_sdi_frame) in sdi_frames.iter().enumerate().take(regions.len()) {
+        if !is_sdi_frame {
+            in_window = false;
+            continue;
+        }
+
+        let frame_number = i as i32 - 123; // Frames start at -123
+        if !in_window {
+            in_window = true;
+            hits.push(SdiHit {
+                start_frame: frame_number,
+                end_frame: frame_number,
+                sdi_inputs: 0,
+                displacement: 0.0,
+                max_legal_displacement: 0.0,
+                outlier: false,
+            });
+        }
+
+        let hit = hits.last_mut().expect("window was just opened");
+        if hit.end_frame != frame_number {
+            if let (Some(prev), Some(pos)) = (positions.get(i - 1), positions.get(i)) {
+                hit.displacement += ((pos.x - prev.x).powi(2) + (pos.y - prev.y).powi(2)).sqrt();
+            }
+        }
+        hit.end_frame = frame_number;
+        if i > 0 && is_sdi_region(regions[i]) && regions[i] != regions[i - 1] {
+            hit.sdi_inputs += 1;
+        }
+    }
+
+    for hit in hits.iter_mut() {
+        let hitlag_frames = (hit.end_frame - hit.start_frame + 1) as usize;
+        hit.max_legal_displacement = max_legal_displacement(hitlag_frames);
+        hit.outlier = hit.displacement > hit.max_legal_displacement + DISPLACEMENT_TOLERANCE;
+    }
+
+    hits
+}
+
+/// Fail hits where the character moved further during hitlag than legal SDI
+/// allows. Only counts against the player with `require_sdi_distance`.
+pub fn check_distance(hits: &[SdiHit]) -> CheckResult {
+    let violations: Vec<Violation> = hits
+        .iter()
+        .filter(|hit| hit.outlier)
+        .map(|hit| {
+            Violation::new(
+                hit.start_frame as f64,
+                format!(
+                    "SDI moved {:.1} units during hitlag (frame {} to {}), legal maximum is {:.1} ({} SDI inputs)",
+                    hit.displacement,
+                    hit.start_frame,
+                    hit.end_frame,
+                    hit.max_legal_displacement,
+                    hit.sdi_inputs,
+                ),
+            )
+        })
+        .collect();
+
+    if violations.is_empty() {
+        CheckResult::pass()
+    } else {
+        CheckResult::fail(violations)
+    }
+}
+
+/// Per-hit SDI inputs and displacement for a player, None if the replay has no hitlag data
+pub fn player_hits(data: &PlayerGameData, ruleset: &Ruleset) -> Option<Vec<SdiHit>> {
+    player_sdi_frames(data).map(|frames| {
+        hits_with(data.stick(STICK_STREAM), &data.positions, &frames, &ruleset.sdi_regions)
+    })
+}
+
+/// Something the SDI engine saw on one frame
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum SdiTraceEvent {
+    /// First frame of a hitlag window SDI is possible in
+    HitlagStart,
+    /// Last frame of the hitlag window
+    HitlagEnd,
+    /// The stick entered a new cardinal or diagonal region during hitlag
+    SdiInput { from: SDIRegion, to: SDIRegion },
+    /// A rule's look-ahead window starting on this frame failed
+    RuleViolation { rule: u8 },
+}
+
+/// Stick regions and SDI events for one frame
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegionTraceFrame {
+    /// Game frame number
+    pub frame: i32,
+    pub coord: Coord,
+    pub sdi_region: SDIRegion,
+    pub joystick_region: JoystickRegion,
+    /// Whether SDI is possible on this frame (always true without hitlag data)
+    pub sdi_frame: bool,
+    pub events: Vec<SdiTraceEvent>,
+}
+
+/// Frame by frame view of what the SDI rules saw for a player, over an
+/// inclusive range of game frames (None for the start or end of the game).
+/// Rule violations are the raw per-rule matches, as with `sdi_raw_violations`.
+pub fn trace(
+    data: &PlayerGameData,
+    ruleset: &Ruleset,
+    start_frame: Option<i32>,
+    end_frame: Option<i32>,
+) -> Vec<RegionTraceFrame> {
+    let coords = data.stick(STICK_STREAM);
+    let geometry = &ruleset.sdi_regions;
+    let regions = regions(coords, geometry);
+    let frames = player_sdi_frames(data);
+    let is_sdi_frame = |i: usize| frames.as_ref().is_none_or(|f| f.get(i).copied().unwrap_or(false));
+
+    let mut events: Vec<Vec<SdiTraceEvent>> = vec![Vec::new(); coords.len()];
+    for i in 0..coords.len() {
+        if frames.is_some() && is_sdi_frame(i) {
+            if i == 0 || !is_sdi_frame(i - 1) {
+                events[i].push(SdiTraceEvent::HitlagStart);
+            }
+            if i > 0 && is_sdi_region(regions[i]) && regions[i] != regions[i - 1] {
+                events[i].push(SdiTraceEvent::SdiInput {
+                    from: regions[i - 1],
+                    to: regions[i],
+                });
+            }
+            if !is_sdi_frame(i + 1) {
+                events[i].push(SdiTraceEvent::HitlagEnd);
+            }
+        }
+    }
+    for (rule, violation) in rule_violations(coords, frames.as_deref(), geometry) {
+        if let Some(frame_events) = events.get_mut(violation.metric as usize) {
+            let event = SdiTraceEvent::RuleViolation { rule };
+            if !frame_events.contains(&event) {
+                frame_events.push(event);
+            }
+        }
+    }
+
+    coords
+        .iter()
+        .zip(events)
+        .enumerate()
+        .map(|(i, (coord, events))| (i as i32 - 123, i, coord, events)) // Frames start at -123
+        .filter(|&(frame, ..)| {
+            start_frame.is_none_or(|start| frame >= start) && end_frame.is_none_or(|end| frame <= end)
+        })
+        .map(|(frame, i, coord, events)| RegionTraceFrame {
+            frame,
+            coord: *coord,
+            sdi_region: regions[i],
+            joystick_region: utils::get_joystick_region(coord.x, coord.y),
+            sdi_frame: is_sdi_frame(i),
+            events,
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -318,6 +702,113 @@ mod tests {
         assert!(!is_region_adjacent(SDIRegion::N, SDIRegion::S));
     }
 
+    /// Mash right and back to neutral every frame
+    fn mash() -> Vec<Coord> {
+        (0..20)
+            .map(|i| Coord::new(if i % 2 == 1 { 1.0 } else { 0.0 }, 0.0))
+            .collect()
+    }
+
+    #[test]
+    fn test_rules_restricted_to_hitlag() {
+        let coords = mash();
+        assert!(!fails_sdi_rule_one(&coords).is_empty());
+        assert!(fails_sdi_rule_one_in_hitlag(&coords, &[false; 20]).is_empty());
+
+        let mut frames = [false; 20];
+        frames[5..9].fill(true);
+        let violations = fails_sdi_rule_one_in_hitlag(&coords, &frames);
+        assert!(!violations.is_empty());
+        assert!(violations.len() < fails_sdi_rule_one(&coords).len());
+    }
+
+    #[test]
+    fn test_sdi_frames_exclude_attacker() {
+        let hitlag = [0.0, 4.0, 3.0, 2.0, 1.0, 0.0];
+        let damaged = [0x0E, 0x50, 0x50, 0x50, 0x50, 0x50];
+        assert_eq!(sdi_frames(&hitlag, &damaged), [false, true, true, true, true, false]);
+
+        let attacking = [0x38; 6];
+        assert!(sdi_frames(&hitlag, &attacking).iter().all(|&f| !f));
+    }
+
+    #[test]
+    fn test_hits_count_sdi_inputs() {
+        let coords = mash();
+        let mut frames = [false; 20];
+        frames[4..8].fill(true);
+        frames[12..14].fill(true);
+
+        let hits = hits(&coords, &[], &frames);
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].start_frame, 4 - 123);
+        assert_eq!(hits[0].end_frame, 7 - 123);
+        assert_eq!(hits[0].sdi_inputs, 2);
+        assert_eq!(hits[1].sdi_inputs, 1);
+    }
+
+    #[test]
+    fn test_custom_region_geometry() {
+        // A half-press is tilt with the default thresholds
+        assert_eq!(get_sdi_region(0.5, 0.0), SDIRegion::TILT);
+        let geometry = RegionGeometry { min_magnitude: 0.5, ..RegionGeometry::SDI };
+        assert_eq!(get_sdi_region_with(0.5, 0.0, &geometry), SDIRegion::E);
+        assert_eq!(get_sdi_region_with(0.2875, 0.2875, &geometry), SDIRegion::DZ);
+
+        let taps: Vec<Coord> = (0..6)
+            .map(|i| Coord::new(if i % 2 == 1 { 0.5 } else { 0.0 }, 0.0))
+            .collect();
+        assert!(!check(&taps).result);
+        assert!(check_frames(&taps, None, &geometry).result);
+    }
+
+    #[test]
+    fn test_overlapping_violations_form_one_incident() {
+        let window = |start: f64, len: usize| Violation::with_evidence(start, String::new(), vec![Coord::new(0.0, 0.0); len]);
+        let violations = vec![
+            (3, window(200.0, 5)),
+            (3, window(201.0, 5)),
+            (1, window(195.0, 10)),
+            (2, window(300.0, 5)),
+        ];
+
+        let incidents = group_incidents(&violations);
+        assert_eq!(incidents.len(), 2);
+        assert_eq!(incidents[0].start_frame, 72);
+        assert_eq!(incidents[0].end_frame, 82);
+        assert_eq!(incidents[0].rules, vec![1, 3]);
+        assert_eq!(incidents[0].count, 3);
+        assert_eq!(incidents[1].rules, vec![2]);
+
+        let result = check_incidents(&incidents, &[]);
+        assert_eq!(result.details.len(), 2);
+        assert_eq!(result.details[0].reason, "Failed SDI rule #1, #3 (3 violations, frame 72 to 82)");
+    }
+
+    #[test]
+    fn test_hit_displacement() {
+        let coords = mash();
+        let mut frames = [false; 20];
+        frames[4..8].fill(true);
+
+        // Two SDIs of 6 units during a 4 frame hitlag
+        let mut positions = vec![Coord::new(0.0, 0.0); 20];
+        positions[5..].fill(Coord::new(6.0, 0.0));
+        positions[7..].fill(Coord::new(12.0, 0.0));
+        let result = hits(&coords, &positions, &frames);
+        assert!((result[0].displacement - 12.0).abs() < 1e-9);
+        assert!((result[0].max_legal_displacement - 12.0).abs() < 1e-9);
+        assert!(!result[0].outlier);
+        assert!(!check_distance(&result).result);
+
+        // Three SDIs in the same window can't be done legally
+        positions[6..].fill(Coord::new(12.0, 0.0));
+        positions[7..].fill(Coord::new(18.0, 0.0));
+        let result = hits(&coords, &positions, &frames);
+        assert!(result[0].outlier);
+        assert_eq!(check_distance(&result).details.len(), 1);
+    }
+
     #[test]
     fn test_diagonal_adjacency() {
         assert!(is_diagonal_adjacent(SDIRegion::NE, SDIRegion::NW));
@@ -0,0 +1,232 @@
+use crate::ruleset::Ruleset;
+use crate::types::{Coord, FrameUse, SnapbackAnalysis, SnapbackFilter, StickStream, Violation};
+
+/// Main stick stream this check reads
+pub const STICK_STREAM: StickStream = StickStream::Raw;
+
+/// Frames this check reads
+pub const FRAMES: FrameUse = FrameUse::Present;
+
+/// Raw axis value (of 80) that counts as a smash input
+const SMASH_RAW: f64 = 65.0;
+
+/// Raw axis value below which the stick is inside the deadzone (matches the 23-unit deadzone)
+const DEADZONE_RAW: f64 = 23.0;
+
+/// Frames the stick may take to fall from a smash into the deadzone for a release
+const RELEASE_FRAMES: usize = 3;
+
+/// Frames after entering the deadzone that are searched for rebound
+const REBOUND_FRAMES: usize = 6;
+
+/// Rebounds at or above this size are deliberate inputs (or unfiltered spikes),
+/// not the small oscillation a filter removes
+const SMALL_REBOUND_MAX: f64 = 16.0;
+
+/// Minimum releases before the filter is judged
+const MIN_RELEASES: usize = 30;
+
+/// Share of releases with a small rebound on an unfiltered stick.
+/// Unmodified sticks in the test data range from 0.17 to 0.65.
+const NATURAL_REBOUND_RATE: f64 = 0.25;
+
+/// Rebound rates below these are considered aggressively / lightly filtered
+const AGGRESSIVE_RATE: f64 = 0.06;
+const LIGHT_RATE: f64 = 0.12;
+
+/// Most release frames shown as evidence
+const MAX_EVIDENCE_FRAMES: usize = 10;
+
+/// One main stick release through neutral
+struct Release {
+    /// Frame the stick entered the deadzone
+    frame: usize,
+    /// Largest excursion past neutral on the opposite side (raw units, 0 if none)
+    rebound: f64,
+}
+
+/// Find releases from a smash input on one axis and measure their rebound.
+///
+/// A release is a smash (|value| >= 65) that falls into the deadzone within
+/// RELEASE_FRAMES and settles there. Excursions to the opposite smash are
+/// deliberate inputs (e.g. dashback) and are skipped.
+fn find_releases(values: &[f64]) -> Vec<Release> {
+    let mut releases = Vec::new();
+
+    for i in 1..values.len() {
+        let held = values[i - 1];
+        if held.abs() < SMASH_RAW {
+            continue;
+        }
+        let side = held.signum();
+        if side * values[i] >= SMASH_RAW {
+            continue;
+        }
+
+        let last = (i + RELEASE_FRAMES).min(values.len());
+        let entered = match (i..last).find(|&j| side * values[j] < DEADZONE_RAW) {
+            Some(j) => j,
+            None => continue,
+        };
+        let settle = entered + REBOUND_FRAMES;
+        match values.get(settle) {
+            Some(v) if v.abs() <= DEADZONE_RAW => {}
+            _ => continue,
+        }
+
+        let rebound = values[entered..settle]
+            .iter()
+            .map(|v| -side * v)
+            .fold(0.0, f64::max);
+        if rebound >= SMASH_RAW {
+            continue;
+        }
+
+        releases.push(Release {
+            frame: entered,
+            rebound,
+        });
+    }
+
+    releases
+}
+
+/// Examine how the main stick returns through neutral after smash inputs.
+///
+/// An unfiltered stick overshoots slightly past center after most releases
+/// (the spring rebound that causes accidental dashbacks). Snapback and dashback
+/// filters suppress that small overshoot, so filtered sticks return to exactly
+/// neutral almost every time. Takes raw int8-scale stick values.
+pub fn analyze(raw_coords: &[Coord], ruleset: &Ruleset) -> SnapbackAnalysis {
+    let xs: Vec<f64> = raw_coords.iter().map(|c| c.x).collect();
+    let ys: Vec<f64> = raw_coords.iter().map(|c| c.y).collect();
+    let mut releases = find_releases(&xs);
+    releases.extend(find_releases(&ys));
+    releases.sort_by_key(|r| r.frame);
+
+    let small: Vec<&Release> = releases
+        .iter()
+        .filter(|r| r.rebound > 0.0 && r.rebound < SMALL_REBOUND_MAX)
+        .collect();
+    let deadzone_crossings = releases.iter().filter(|r| r.rebound >= DEADZONE_RAW).count();
+
+    let rebound_rate = if releases.is_empty() {
+        0.0
+    } else {
+        small.len() as f64 / releases.len() as f64
+    };
+    let mean_rebound = if small.is_empty() {
+        0.0
+    } else {
+        small.iter().map(|r| r.rebound).sum::<f64>() / small.len() as f64
+    };
+
+    let (filter, strength) = if releases.len() < MIN_RELEASES {
+        (SnapbackFilter::Unknown, 0.0)
+    } else {
+        let strength = (1.0 - rebound_rate / NATURAL_REBOUND_RATE).clamp(0.0, 1.0);
+        let filter = if rebound_rate < AGGRESSIVE_RATE {
+            SnapbackFilter::Aggressive
+        } else if rebound_rate < LIGHT_RATE {
+            SnapbackFilter::Light
+        } else {
+            SnapbackFilter::Unfiltered
+        };
+        (filter, strength)
+    };
+
+    let mut violations = Vec::new();
+    if filter != SnapbackFilter::Unknown && !ruleset.allowed_snapback_filters.contains(&filter) {
+        let evidence_frames: Vec<usize> = releases
+            .iter()
+            .filter(|r| r.rebound == 0.0)
+            .take(MAX_EVIDENCE_FRAMES)
+            .map(|r| r.frame)
+            .collect();
+        violations.push(Violation::with_evidence(
+            evidence_frames.first().copied().unwrap_or(0) as f64,
+            format!(
+                "{:?} snapback filtering (strength {:.2}) is not allowed: {} of {} releases rebound past neutral ({:.0}%), expected around {:.0}%",
+                filter,
+                strength,
+                small.len(),
+                releases.len(),
+                rebound_rate * 100.0,
+                NATURAL_REBOUND_RATE * 100.0,
+            ),
+            evidence_frames.iter().map(|&f| raw_coords[f]).collect(),
+        ));
+    }
+
+    SnapbackAnalysis {
+        pass: violations.is_empty(),
+        filter,
+        strength,
+        releases: releases.len(),
+        rebounds: small.len(),
+        rebound_rate,
+        mean_rebound,
+        deadzone_crossings,
+        violations,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Repeated right smashes released through neutral, with the given rebound per release
+    fn releases_with(rebounds: &[f64]) -> Vec<Coord> {
+        let mut coords = Vec::new();
+        for &rebound in rebounds {
+            for x in [0.0, 0.0, 40.0, 80.0, 80.0, 80.0, 35.0, 0.0, -rebound, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0] {
+                coords.push(Coord::new(x, 0.0));
+            }
+        }
+        coords
+    }
+
+    #[test]
+    fn test_unfiltered_stick_rebounds() {
+        let rebounds: Vec<f64> = (0..40).map(|i| if i % 3 == 0 { 8.0 } else { 0.0 }).collect();
+        let analysis = analyze(&releases_with(&rebounds), &Ruleset::default());
+        assert_eq!(analysis.releases, 40);
+        assert_eq!(analysis.rebounds, 14);
+        assert_eq!(analysis.filter, SnapbackFilter::Unfiltered);
+        assert!((analysis.mean_rebound - 8.0).abs() < 1e-9);
+        assert!(analysis.pass);
+    }
+
+    #[test]
+    fn test_filtered_stick_returns_to_neutral() {
+        let analysis = analyze(&releases_with(&[0.0; 40]), &Ruleset::default());
+        assert_eq!(analysis.filter, SnapbackFilter::Aggressive);
+        assert!((analysis.strength - 1.0).abs() < 1e-9);
+        assert!(analysis.pass, "Default ruleset allows snapback filters");
+
+        let strict = Ruleset {
+            allowed_snapback_filters: vec![SnapbackFilter::Unfiltered, SnapbackFilter::Light],
+            ..Ruleset::default()
+        };
+        let analysis = analyze(&releases_with(&[0.0; 40]), &strict);
+        assert!(!analysis.pass);
+        assert_eq!(analysis.violations[0].evidence.len(), MAX_EVIDENCE_FRAMES);
+        // First release enters the deadzone on frame 7
+        assert_eq!(analysis.violations[0].metric, 7.0);
+        assert!(analysis.violations[0].reason.contains("strength 1.00"));
+    }
+
+    #[test]
+    fn test_dashback_is_not_a_release() {
+        // Smash right then straight to smash left: a deliberate input, not a rebound
+        let mut coords = Vec::new();
+        for _ in 0..40 {
+            for x in [80.0, 80.0, 0.0, -80.0, -80.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0] {
+                coords.push(Coord::new(x, 0.0));
+            }
+        }
+        let analysis = analyze(&coords, &Ruleset::default());
+        assert_eq!(analysis.rebounds, 0);
+        assert_eq!(analysis.deadzone_crossings, 0);
+    }
+}
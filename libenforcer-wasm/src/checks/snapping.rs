@@ -0,0 +1,281 @@
+use crate::ruleset::Ruleset;
+use crate::types::{Coord, FrameUse, SnapAxis, SnapKind, SnappingAnalysis, StickStream, Violation};
+
+/// Main stick stream this check reads. Snapping is a property of the
+/// controller, so it is measured on the raw hardware values.
+pub const STICK_STREAM: StickStream = StickStream::Raw;
+
+/// Frames this check reads
+pub const FRAMES: FrameUse = FrameUse::Present;
+
+/// Only coordinates in this magnitude range are measured. The deadzone is
+/// excluded because one raw unit there spans a wide angle, and the rim is
+/// excluded so that notch dwell (legal notch calibration) doesn't read as snapping.
+const MIN_MAGNITUDE: f64 = 0.375; // 30/80
+const MAX_MAGNITUDE: f64 = 0.875; // 70/80
+
+/// Perpendicular distance from an axis below which a coordinate sits on it (half a raw unit)
+const ON_AXIS_DISTANCE: f64 = 0.5 / 80.0;
+
+/// Width of the band beside an axis that a snapping firmware empties out.
+/// Matches the 0.08 off-axis threshold of the original GoomWave test.
+const NEAR_AXIS_DISTANCE: f64 = 0.08;
+
+/// Minimum coordinates on or near an axis before its snapping is judged
+const MIN_AXIS_POINTS: usize = 30;
+
+/// Share of an axis band that must sit exactly on the axis to report it as snapping.
+/// A natural stick spreads over the band (legal sticks stay below 0.45).
+const SNAP_SCORE_THRESHOLD: f64 = 0.6;
+
+/// Angle histogram bin width (degrees) used to look for arbitrary snapping angles
+const PEAK_BIN_DEGREES: f64 = 1.0;
+
+/// A bin must hold this many times the median of its neighbors to be a candidate angle
+const PEAK_RATIO: f64 = 4.0;
+
+/// Neighboring bins (each side) compared against a candidate peak
+const PEAK_NEIGHBORHOOD: usize = 10;
+
+/// Most evidence frames kept per snapping axis
+const MAX_EVIDENCE_FRAMES: usize = 20;
+
+fn in_measured_range(coord: &Coord) -> bool {
+    let magnitude = (coord.x * coord.x + coord.y * coord.y).sqrt();
+    (MIN_MAGNITUDE..=MAX_MAGNITUDE).contains(&magnitude)
+}
+
+/// Measure how strongly coordinates collapse onto the ray at `angle` degrees.
+pub fn measure_axis(coords: &[Coord], angle: f64, kind: SnapKind) -> SnapAxis {
+    let (sin, cos) = angle.to_radians().sin_cos();
+
+    let mut on_axis = 0;
+    let mut near_axis = 0;
+    let mut frames = Vec::new();
+
+    for (i, coord) in coords.iter().enumerate() {
+        if !in_measured_range(coord) {
+            continue;
+        }
+        let along = coord.x * cos + coord.y * sin;
+        if along <= 0.0 {
+            continue;
+        }
+        let distance = (coord.y * cos - coord.x * sin).abs();
+
+        if distance < ON_AXIS_DISTANCE {
+            on_axis += 1;
+            if frames.len() < MAX_EVIDENCE_FRAMES {
+                frames.push(i);
+            }
+        } else if distance <= NEAR_AXIS_DISTANCE {
+            near_axis += 1;
+        }
+    }
+
+    let total = on_axis + near_axis;
+    let score = if total == 0 {
+        0.0
+    } else {
+        on_axis as f64 / total as f64
+    };
+
+    SnapAxis {
+        angle,
+        kind,
+        on_axis,
+        near_axis,
+        score,
+        frames,
+    }
+}
+
+/// Find angles (other than cardinals and diagonals) where coordinates pile up
+fn candidate_angles(coords: &[Coord]) -> Vec<f64> {
+    let num_bins = (360.0 / PEAK_BIN_DEGREES) as usize;
+    let mut bins = vec![0usize; num_bins];
+    let mut sums = vec![0.0f64; num_bins];
+
+    for coord in coords.iter().filter(|c| in_measured_range(c)) {
+        let angle = coord.y.atan2(coord.x).to_degrees().rem_euclid(360.0);
+        let bin = ((angle / PEAK_BIN_DEGREES) as usize).min(num_bins - 1);
+        bins[bin] += 1;
+        sums[bin] += angle;
+    }
+
+    let mut candidates = Vec::new();
+    for bin in 0..num_bins {
+        if bins[bin] < MIN_AXIS_POINTS {
+            continue;
+        }
+        let mut neighbors: Vec<usize> = (1..=PEAK_NEIGHBORHOOD)
+            .flat_map(|k| [bins[(bin + k) % num_bins], bins[(bin + num_bins - k) % num_bins]])
+            .collect();
+        neighbors.sort_unstable();
+        let median = neighbors[neighbors.len() / 2].max(1);
+        if (bins[bin] as f64) < PEAK_RATIO * median as f64 {
+            continue;
+        }
+
+        let angle = sums[bin] / bins[bin] as f64;
+        // Cardinals and diagonals are measured separately
+        let offset = angle.rem_euclid(45.0);
+        if offset.min(45.0 - offset) > 5.0 {
+            candidates.push(angle);
+        }
+    }
+
+    candidates
+}
+
+/// Density-based snapping detector.
+///
+/// For each cardinal, each diagonal and any other angle where coordinates pile
+/// up, compare the coordinates exactly on that axis with those in the band
+/// beside it. Firmware that snaps near-axis inputs onto the axis (e.g. GoomWave)
+/// empties the band, so nearly everything lands on the axis itself. A natural
+/// stick spreads across the band and scores low.
+///
+/// Snapping axes are always reported. Some legal controllers snap their
+/// cardinals too, so they only fail when the ruleset sets a score limit.
+///
+/// Coordinates are in normalized units (raw / 80).
+pub fn analyze(coords: &[Coord], ruleset: &Ruleset) -> SnappingAnalysis {
+    let mut measured: Vec<SnapAxis> = Vec::new();
+    for k in 0..8 {
+        let angle = k as f64 * 45.0;
+        let kind = if k % 2 == 0 { SnapKind::Cardinal } else { SnapKind::Diagonal };
+        measured.push(measure_axis(coords, angle, kind));
+    }
+    for angle in candidate_angles(coords) {
+        measured.push(measure_axis(coords, angle, SnapKind::Arbitrary));
+    }
+
+    let judged: Vec<SnapAxis> = measured
+        .into_iter()
+        .filter(|axis| axis.on_axis + axis.near_axis >= MIN_AXIS_POINTS)
+        .collect();
+
+    let score = judged.iter().map(|axis| axis.score).fold(0.0, f64::max);
+    let axes: Vec<SnapAxis> = judged
+        .into_iter()
+        .filter(|axis| axis.score >= SNAP_SCORE_THRESHOLD)
+        .collect();
+
+    let violations: Vec<Violation> = axes
+        .iter()
+        .filter(|axis| ruleset.max_snapping_score.is_some_and(|max| axis.score > max))
+        .map(|axis| {
+            Violation::with_evidence(
+                axis.frames.first().copied().unwrap_or(0) as f64,
+                format!(
+                    "{:?} axis at {:.1}° snaps: {} of {} nearby coordinates land exactly on it ({:.0}%)",
+                    axis.kind,
+                    axis.angle,
+                    axis.on_axis,
+                    axis.on_axis + axis.near_axis,
+                    axis.score * 100.0,
+                ),
+                axis.frames.iter().map(|&f| coords[f]).collect(),
+            )
+        })
+        .collect();
+
+    SnappingAnalysis {
+        pass: violations.is_empty(),
+        score,
+        axes,
+        violations,
+    }
+}
+
+/// Scale raw int8-scale stick values to normalized units
+pub fn normalize_raw(raw_coords: &[Coord]) -> Vec<Coord> {
+    raw_coords
+        .iter()
+        .map(|c| Coord::new(c.x / 80.0, c.y / 80.0))
+        .collect()
+}
+
+/// Run the snapping detector on raw int8-scale stick values
+pub fn analyze_raw(raw_coords: &[Coord], ruleset: &Ruleset) -> SnappingAnalysis {
+    analyze(&normalize_raw(raw_coords), ruleset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Points sweeping a ring of the given radius, one every `step` degrees,
+    /// with off-axis offsets below `snap` (normalized units) snapped onto the cardinal
+    fn ring(radius: f64, step: f64, snap: f64) -> Vec<Coord> {
+        let mut coords = Vec::new();
+        let mut deg: f64 = 0.0;
+        while deg < 360.0 {
+            let (sin, cos) = deg.to_radians().sin_cos();
+            let mut x = ((radius * cos) * 80.0).round() / 80.0;
+            let mut y = ((radius * sin) * 80.0).round() / 80.0;
+            if x.abs() < snap {
+                x = 0.0;
+            }
+            if y.abs() < snap {
+                y = 0.0;
+            }
+            coords.push(Coord::new(x, y));
+            deg += step;
+        }
+        coords
+    }
+
+    fn natural(snap: f64) -> Vec<Coord> {
+        [0.45, 0.55, 0.65, 0.75]
+            .iter()
+            .flat_map(|&r| ring(r, 0.25, snap))
+            .collect()
+    }
+
+    #[test]
+    fn test_natural_stick_does_not_snap() {
+        let analysis = analyze(&natural(0.0), &Ruleset::default());
+        assert!(analysis.pass);
+        assert!(analysis.axes.is_empty());
+        assert!(analysis.score < SNAP_SCORE_THRESHOLD);
+    }
+
+    #[test]
+    fn test_cardinal_snapping_is_reported() {
+        let analysis = analyze(&natural(0.1), &Ruleset::default());
+        assert!(analysis.pass, "Default ruleset does not limit snapping");
+        assert_eq!(analysis.axes.len(), 4);
+        assert!(analysis.axes.iter().all(|a| a.kind == SnapKind::Cardinal));
+        assert!((analysis.score - 1.0).abs() < 1e-9);
+
+        let strict = Ruleset {
+            max_snapping_score: Some(0.8),
+            ..Ruleset::default()
+        };
+        let analysis = analyze(&natural(0.1), &strict);
+        assert!(!analysis.pass);
+        assert_eq!(analysis.violations.len(), 4);
+        assert!(!analysis.violations[0].evidence.is_empty());
+    }
+
+    #[test]
+    fn test_arbitrary_angle_snapping() {
+        let mut coords = natural(0.0);
+        // Pull everything within 8° of 30° onto the 30° ray
+        let (sin, cos) = 30f64.to_radians().sin_cos();
+        for coord in coords.iter_mut() {
+            let angle = coord.y.atan2(coord.x).to_degrees();
+            if (angle - 30.0).abs() < 8.0 {
+                let r = (coord.x * coord.x + coord.y * coord.y).sqrt();
+                *coord = Coord::new(r * cos, r * sin);
+            }
+        }
+
+        let analysis = analyze(&coords, &Ruleset::default());
+        assert_eq!(analysis.axes.len(), 1);
+        assert_eq!(analysis.axes[0].kind, SnapKind::Arbitrary);
+        assert!((analysis.axes[0].angle - 30.0).abs() < 0.5);
+    }
+}
@@ -0,0 +1,313 @@
+use crate::ruleset::Ruleset;
+use crate::types::{
+    Coord, FrameUse, SocdAnalysis, SocdAxisAnalysis, SocdMode, StickStream, Violation,
+};
+
+/// Main stick stream this check reads
+pub const STICK_STREAM: StickStream = StickStream::Processed;
+
+/// Frames this check reads
+pub const FRAMES: FrameUse = FrameUse::Present;
+
+/// Axis value at which a direction counts as pressed.
+/// Matches the deadzone threshold used by getJoystickRegion().
+const PRESS_THRESHOLD: f64 = 0.2875;
+
+/// Longest neutral gap (in frames) between opposite directions that still
+/// counts as one opposite-direction change rather than two separate inputs
+const NEUTRAL_GAP_FRAMES: usize = 3;
+
+/// Longest opposite-direction tap (in frames) used to tell reactivation apart
+/// from release-to-neutral. Longer holds usually mean both buttons were released.
+const SHORT_TAP_FRAMES: usize = 8;
+
+/// Minimum opposite-direction changes on an axis before a mode is inferred
+const MIN_OPPOSING_EVENTS: usize = 10;
+
+/// Minimum reactivations before last-input-wins is inferred. Dashdancing
+/// on a second-input-priority box also flips straight back now and then,
+/// so a handful of reactivations is not evidence.
+const MIN_REACTIVATIONS: usize = 10;
+
+/// Share of short taps that must end in a reactivation (rather than a
+/// release to neutral) for the controller to be considered last-input-wins
+const REACTIVATION_RATIO: f64 = 0.8;
+
+/// Share of opposite-direction changes that must be direct flips for the
+/// controller to be considered as overriding (rather than neutralizing) SOCD
+const OVERRIDE_RATIO: f64 = 0.2;
+
+/// Which stick axis to analyze
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+impl Axis {
+    fn value(&self, coord: &Coord) -> f64 {
+        match self {
+            Axis::Horizontal => coord.x,
+            Axis::Vertical => coord.y,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Axis::Horizontal => "left/right",
+            Axis::Vertical => "up/down",
+        }
+    }
+}
+
+/// A run of consecutive frames with the same pressed direction on one axis
+struct Segment {
+    /// -1, 0 (neutral) or +1
+    direction: i8,
+    start_frame: usize,
+    len: usize,
+}
+
+fn direction_of(value: f64) -> i8 {
+    if value >= PRESS_THRESHOLD {
+        1
+    } else if value <= -PRESS_THRESHOLD {
+        -1
+    } else {
+        0
+    }
+}
+
+/// Run-length encode the pressed direction of an axis
+fn segments(coords: &[Coord], axis: Axis) -> Vec<Segment> {
+    let mut segments: Vec<Segment> = Vec::new();
+
+    for (i, coord) in coords.iter().enumerate() {
+        let direction = direction_of(axis.value(coord));
+        match segments.last_mut() {
+            Some(last) if last.direction == direction => last.len += 1,
+            _ => segments.push(Segment {
+                direction,
+                start_frame: i,
+                len: 1,
+            }),
+        }
+    }
+
+    segments
+}
+
+/// Infer the SOCD mode of one axis.
+/// Returns the axis analysis plus the frames of any reactivation events,
+/// which are the evidence for last-input-wins behavior.
+pub fn analyze_axis(coords: &[Coord], axis: Axis) -> (SocdAxisAnalysis, Vec<usize>) {
+    let segments = segments(coords, axis);
+
+    let mut direct_flips = 0;
+    let mut neutral_crossings = 0;
+    let mut reactivations = 0;
+    let mut releases_to_neutral = 0;
+    let mut reactivation_frames = Vec::new();
+    // Set when the current segment is the tail of a reactivation, so the
+    // flip back to the held direction isn't judged as a tap of its own
+    let mut returning_from_tap = false;
+
+    for (k, held) in segments.iter().enumerate() {
+        let is_return = std::mem::take(&mut returning_from_tap);
+        if held.direction == 0 {
+            continue;
+        }
+        let next = match segments.get(k + 1) {
+            Some(next) => next,
+            None => break,
+        };
+
+        if next.direction == -held.direction {
+            // Jumped straight to the opposite direction: the newer input overrode the held one
+            direct_flips += 1;
+
+            // A short opposite tap shows what happens when the newer input is released
+            if !is_return && next.len <= SHORT_TAP_FRAMES {
+                match segments.get(k + 2) {
+                    Some(after) if after.direction == held.direction => {
+                        reactivations += 1;
+                        reactivation_frames.push(next.start_frame);
+                        returning_from_tap = true;
+                    }
+                    Some(after) if after.direction == 0 => releases_to_neutral += 1,
+                    _ => {}
+                }
+            }
+        } else if next.direction == 0 && next.len <= NEUTRAL_GAP_FRAMES {
+            if let Some(after) = segments.get(k + 2) {
+                if after.direction == -held.direction {
+                    neutral_crossings += 1;
+                }
+            }
+        }
+    }
+
+    let opposing = direct_flips + neutral_crossings;
+    let short_taps = reactivations + releases_to_neutral;
+
+    let mode = if opposing < MIN_OPPOSING_EVENTS {
+        SocdMode::Unknown
+    } else if (direct_flips as f64 / opposing as f64) < OVERRIDE_RATIO {
+        SocdMode::Neutral
+    } else if reactivations >= MIN_REACTIVATIONS
+        && reactivations as f64 / short_taps as f64 >= REACTIVATION_RATIO
+    {
+        SocdMode::LastInputWins
+    } else {
+        // Overrides are present but reactivation is not established
+        SocdMode::SecondInputPriority
+    };
+
+    (
+        SocdAxisAnalysis {
+            mode,
+            direct_flips,
+            neutral_crossings,
+            reactivations,
+            releases_to_neutral,
+        },
+        reactivation_frames,
+    )
+}
+
+/// Build a violation for an axis whose inferred mode the ruleset does not allow
+fn axis_violation(
+    coords: &[Coord],
+    axis: Axis,
+    analysis: &SocdAxisAnalysis,
+    reactivation_frames: &[usize],
+) -> Violation {
+    let reason = format!(
+        "SOCD mode {:?} on the {} axis is not allowed ({} direct flips, {} neutral crossings, {} reactivations)",
+        analysis.mode,
+        axis.name(),
+        analysis.direct_flips,
+        analysis.neutral_crossings,
+        analysis.reactivations,
+    );
+
+    match reactivation_frames.first() {
+        Some(&frame) => {
+            let start = frame.saturating_sub(1);
+            let end = (frame + SHORT_TAP_FRAMES + 1).min(coords.len());
+            Violation::with_evidence(frame as f64, reason, coords[start..end].to_vec())
+        }
+        None => Violation::new(0.0, reason),
+    }
+}
+
+/// Infer the SOCD resolution mode on both main stick axes and flag
+/// any mode the ruleset disallows. An Unknown mode never fails, and
+/// violations only affect `is_legal` with `require_socd_mode`.
+pub fn analyze(coords: &[Coord], ruleset: &Ruleset) -> SocdAnalysis {
+    let (horizontal, horizontal_frames) = analyze_axis(coords, Axis::Horizontal);
+    let (vertical, vertical_frames) = analyze_axis(coords, Axis::Vertical);
+
+    let mut violations = Vec::new();
+
+    if horizontal.mode != SocdMode::Unknown
+        && !ruleset.allowed_socd_horizontal.contains(&horizontal.mode)
+    {
+        violations.push(axis_violation(coords, Axis::Horizontal, &horizontal, &horizontal_frames));
+    }
+    if vertical.mode != SocdMode::Unknown
+        && !ruleset.allowed_socd_vertical.contains(&vertical.mode)
+    {
+        violations.push(axis_violation(coords, Axis::Vertical, &vertical, &vertical_frames));
+    }
+
+    SocdAnalysis {
+        pass: violations.is_empty(),
+        horizontal,
+        vertical,
+        violations,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn x_coords(xs: &[f64]) -> Vec<Coord> {
+        xs.iter().map(|&x| Coord::new(x, 0.0)).collect()
+    }
+
+    /// Repeat a pattern of X values with neutral padding in between
+    fn repeat_pattern(pattern: &[f64], times: usize) -> Vec<Coord> {
+        let mut xs = Vec::new();
+        for _ in 0..times {
+            xs.extend_from_slice(&[0.0; 10]);
+            xs.extend_from_slice(pattern);
+        }
+        xs.extend_from_slice(&[0.0; 10]);
+        x_coords(&xs)
+    }
+
+    #[test]
+    fn test_unknown_without_opposing_inputs() {
+        let coords = repeat_pattern(&[-1.0, -1.0, -1.0], 20);
+        let (axis, _) = analyze_axis(&coords, Axis::Horizontal);
+        assert_eq!(axis.mode, SocdMode::Unknown);
+        assert_eq!(axis.direct_flips, 0);
+    }
+
+    #[test]
+    fn test_neutral_socd() {
+        // Hold left, press right (neutral), release left (right)
+        let coords = repeat_pattern(&[-1.0, -1.0, -1.0, 0.0, 0.0, 1.0, 1.0, 1.0], 15);
+        let (axis, _) = analyze_axis(&coords, Axis::Horizontal);
+        assert_eq!(axis.mode, SocdMode::Neutral);
+        assert_eq!(axis.neutral_crossings, 15);
+    }
+
+    #[test]
+    fn test_last_input_wins() {
+        // Hold left, tap right, release right (back to left)
+        let coords = repeat_pattern(&[-1.0, -1.0, -1.0, 1.0, 1.0, -1.0, -1.0, -1.0], 15);
+        let (axis, frames) = analyze_axis(&coords, Axis::Horizontal);
+        assert_eq!(axis.mode, SocdMode::LastInputWins);
+        assert_eq!(axis.reactivations, 15);
+        assert_eq!(frames.len(), 15);
+    }
+
+    #[test]
+    fn test_dashdance_flips_are_not_last_input_wins() {
+        // Mostly taps released to neutral, with some quick flips back
+        let mut coords = repeat_pattern(&[-1.0, -1.0, -1.0, 1.0, 1.0, 0.0, 0.0], 11);
+        coords.extend(repeat_pattern(&[-1.0, -1.0, -1.0, 1.0, 1.0, -1.0, -1.0, -1.0], 12));
+        let (axis, _) = analyze_axis(&coords, Axis::Horizontal);
+        assert_eq!(axis.reactivations, 12);
+        assert_eq!(axis.mode, SocdMode::SecondInputPriority);
+    }
+
+    #[test]
+    fn test_second_input_priority() {
+        // Hold left, tap right, release right (neutral, left is not reactivated)
+        let coords = repeat_pattern(&[-1.0, -1.0, -1.0, 1.0, 1.0, 0.0, 0.0], 15);
+        let (axis, _) = analyze_axis(&coords, Axis::Horizontal);
+        assert_eq!(axis.mode, SocdMode::SecondInputPriority);
+        assert_eq!(axis.releases_to_neutral, 15);
+    }
+
+    #[test]
+    fn test_ruleset_flags_disallowed_mode() {
+        let coords = repeat_pattern(&[-1.0, -1.0, -1.0, 1.0, 1.0, -1.0, -1.0, -1.0], 15);
+
+        let analysis = analyze(&coords, &Ruleset::default());
+        assert!(!analysis.pass);
+        assert_eq!(analysis.violations.len(), 1);
+        assert!(!analysis.violations[0].evidence.is_empty());
+        assert_eq!(analysis.vertical.mode, SocdMode::Unknown);
+
+        let permissive = Ruleset {
+            allowed_socd_horizontal: vec![SocdMode::LastInputWins],
+            ..Ruleset::default()
+        };
+        assert!(analyze(&coords, &permissive).pass);
+    }
+}
@@ -0,0 +1,266 @@
+use crate::action_states::{self, ATTACK_HI3, ATTACK_HI4, KNEE_BEND, SQUAT_WAIT};
+use crate::parser::PlayerGameData;
+use crate::ruleset::Ruleset;
+use crate::types::{
+    ControllerType, Coord, FrameUse, StickStream, TransitionAnalysis, TransitionRule,
+    TransitionRuleResult, Violation,
+};
+
+/// Main stick stream this check reads for evidence and exemptions.
+/// Verdicts rest on action states, so approximated sticks don't degrade it.
+pub const STICK_STREAM: StickStream = StickStream::Processed;
+
+/// Frames this check reads
+pub const FRAMES: FrameUse = FrameUse::Aligned;
+
+/// Frames between crouching and an up attack below which a box input is nerfed.
+/// Human reaction time makes transitions under 4 frames (<=3) impossible on analog.
+const CROUCH_UP_ATTACK_MIN_FRAMES: i32 = 4;
+
+/// Frames between crouching and a tap jump below which a box skipped neutral.
+/// Nerfed boxes take at least 2 frames, analog sticks 4 or more.
+const CROUCH_TAP_JUMP_MIN_FRAMES: i32 = 2;
+
+/// Main stick Y at which the game reads a tap jump
+const TAP_JUMP_Y: f64 = 0.6625;
+
+/// Crouch straight into uptilt, the original crouch-uptilt check
+pub fn crouch_uptilt_rule() -> TransitionRule {
+    TransitionRule {
+        name: "Crouch-uptilt".to_string(),
+        from_states: vec![SQUAT_WAIT],
+        to_states: vec![ATTACK_HI3],
+        min_frames: CROUCH_UP_ATTACK_MIN_FRAMES,
+        controller_types: vec![ControllerType::Box],
+        main_stick_only: false,
+        min_stick_y: None,
+    }
+}
+
+/// Crouch straight into a main stick upsmash. Upsmashes on the C-stick are
+/// exempt since they never pass through the main stick's down-to-up travel.
+pub fn crouch_upsmash_rule() -> TransitionRule {
+    TransitionRule {
+        name: "Crouch-upsmash".to_string(),
+        from_states: vec![SQUAT_WAIT],
+        to_states: vec![ATTACK_HI4],
+        min_frames: CROUCH_UP_ATTACK_MIN_FRAMES,
+        controller_types: vec![ControllerType::Box],
+        main_stick_only: true,
+        min_stick_y: None,
+    }
+}
+
+/// Crouch straight into a tap jump, skipping the neutral frame between
+/// down and up. Jumps from a button are exempt.
+pub fn crouch_tap_jump_rule() -> TransitionRule {
+    TransitionRule {
+        name: "Crouch-tap-jump".to_string(),
+        from_states: vec![SQUAT_WAIT],
+        to_states: vec![KNEE_BEND],
+        min_frames: CROUCH_TAP_JUMP_MIN_FRAMES,
+        controller_types: vec![ControllerType::Box],
+        main_stick_only: true,
+        min_stick_y: Some(TAP_JUMP_Y),
+    }
+}
+
+/// Rules used by the default ruleset
+pub fn default_rules() -> Vec<TransitionRule> {
+    vec![crouch_uptilt_rule(), crouch_upsmash_rule(), crouch_tap_jump_rule()]
+}
+
+/// Whether a transition entered on this frame is exempt from the rule
+fn is_exempt(rule: &TransitionRule, coords: &[Coord], c_coords: &[Coord], index: usize) -> bool {
+    let stick_too_low = rule
+        .min_stick_y
+        .is_some_and(|min| coords.get(index).is_none_or(|c| c.y < min));
+    (rule.main_stick_only && cstick_pressed(c_coords, index)) || stick_too_low
+}
+
+fn cstick_pressed(c_coords: &[Coord], index: usize) -> bool {
+    c_coords
+        .get(index)
+        .is_some_and(|c| c.x != 0.0 || c.y != 0.0)
+}
+
+/// Evaluate one rule over a player's action states.
+///
+/// Every frame spent in a `to_states` state within `min_frames` of the last
+/// `from_states` frame is a violation, with the coordinates from the last
+/// `from_states` frame onward as evidence. `character` (from game start)
+/// is used to name character-specific states in the reason.
+pub fn evaluate(
+    rule: &TransitionRule,
+    coords: &[Coord],
+    c_coords: &[Coord],
+    action_states: &[u16],
+    character: Option<u8>,
+) -> TransitionRuleResult {
+    let mut violations = Vec::new();
+    let mut last_from_frame: i32 = -124;
+    let mut last_from_state = 0;
+    let mut in_to_state = false;
+    let mut exempt = false;
+
+    for (i, &action_state) in action_states.iter().enumerate() {
+        let frame_number = i as i32 - 123; // Frames start at -123
+
+        if rule.from_states.contains(&action_state) {
+            last_from_frame = frame_number;
+            last_from_state = action_state;
+        }
+
+        if !rule.to_states.contains(&action_state) {
+            in_to_state = false;
+            continue;
+        }
+        if !in_to_state {
+            in_to_state = true;
+            exempt = is_exempt(rule, coords, c_coords, i);
+        }
+        if exempt {
+            continue;
+        }
+
+        let frames_since = frame_number - last_from_frame;
+        if frames_since < rule.min_frames {
+            let evidence_start = (last_from_frame + 123) as usize;
+            let evidence_end = (evidence_start + rule.min_frames as usize).min(coords.len());
+            let evidence = coords
+                .get(evidence_start..evidence_end)
+                .unwrap_or_default()
+                .to_vec();
+
+            violations.push(Violation::with_evidence(
+                last_from_frame as f64,
+                format!(
+                    "{} occurred within {} frames ({} at frame {} to {} at frame {})",
+                    rule.name,
+                    frames_since,
+                    action_states::display_name(last_from_state, character),
+                    last_from_frame,
+                    action_states::display_name(action_state, character),
+                    frame_number
+                ),
+                evidence,
+            ));
+        }
+    }
+
+    TransitionRuleResult {
+        name: rule.name.clone(),
+        pass: violations.is_empty(),
+        violations,
+    }
+}
+
+/// Run every ruleset transition rule that applies to the controller type
+pub fn analyze(
+    data: &PlayerGameData,
+    controller_type: ControllerType,
+    ruleset: &Ruleset,
+) -> TransitionAnalysis {
+    let rules: Vec<TransitionRuleResult> = ruleset
+        .transition_rules
+        .iter()
+        .filter(|rule| rule.controller_types.contains(&controller_type))
+        .map(|rule| {
+            evaluate(
+                rule,
+                data.stick(STICK_STREAM),
+                &data.c_coords,
+                &data.action_states,
+                data.character,
+            )
+        })
+        .collect();
+
+    TransitionAnalysis {
+        pass: rules.iter().all(|rule| rule.pass),
+        rules,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::player_data;
+
+    fn states_with(entries: &[(usize, u16)]) -> Vec<u16> {
+        let mut action_states = vec![0; 200];
+        for &(frame, state) in entries {
+            action_states[frame + 123] = state;
+        }
+        action_states
+    }
+
+    #[test]
+    fn test_each_rule_is_reported() {
+        let mut data = player_data(vec![Coord::new(0.0, 0.0); 200]);
+        // Fast crouch-upsmash, slow crouch-uptilt
+        data.action_states = states_with(&[(0, SQUAT_WAIT), (2, ATTACK_HI4), (20, SQUAT_WAIT), (30, ATTACK_HI3)]);
+
+        let analysis = analyze(&data, ControllerType::Box, &Ruleset::default());
+        assert!(!analysis.pass);
+        assert_eq!(analysis.rules.len(), 3);
+        assert!(analysis.rules[0].pass);
+        assert!(!analysis.rules[1].pass);
+        assert!(analysis.rules[2].pass);
+        assert!(analysis.rules[1].violations[0].reason.starts_with("Crouch-upsmash"));
+
+        let analog = analyze(&data, ControllerType::Analog, &Ruleset::default());
+        assert!(analog.pass);
+        assert!(analog.rules.is_empty());
+    }
+
+    #[test]
+    fn test_cstick_transition_is_exempt() {
+        let coords = vec![Coord::new(0.0, 0.0); 200];
+        let mut c_coords = coords.clone();
+        c_coords[2 + 123] = Coord::new(0.0, 1.0);
+        let action_states = states_with(&[(0, SQUAT_WAIT), (2, ATTACK_HI4), (3, ATTACK_HI4)]);
+
+        let result = evaluate(&crouch_upsmash_rule(), &coords, &c_coords, &action_states, None);
+        assert!(result.pass);
+
+        let result = evaluate(&crouch_upsmash_rule(), &coords, &coords, &action_states, None);
+        assert_eq!(result.violations.len(), 2);
+    }
+
+    #[test]
+    fn test_button_jump_is_exempt() {
+        let c_coords = vec![Coord::new(0.0, 0.0); 200];
+        let mut coords = c_coords.clone();
+        let action_states = states_with(&[(0, SQUAT_WAIT), (1, KNEE_BEND)]);
+
+        let result = evaluate(&crouch_tap_jump_rule(), &coords, &c_coords, &action_states, None);
+        assert!(result.pass);
+
+        coords[1 + 123] = Coord::new(0.0, 1.0);
+        let result = evaluate(&crouch_tap_jump_rule(), &coords, &c_coords, &action_states, None);
+        assert_eq!(result.violations.len(), 1);
+        assert!(result.violations[0].reason.starts_with("Crouch-tap-jump occurred within 1 frames"));
+    }
+
+    #[test]
+    fn test_custom_rule() {
+        let coords = vec![Coord::new(0.0, 0.0); 200];
+        let rule = TransitionRule {
+            name: "Dash-turn".to_string(),
+            from_states: vec![action_states::DASH],
+            to_states: vec![action_states::TURN],
+            min_frames: 2,
+            controller_types: vec![ControllerType::Analog],
+            main_stick_only: false,
+            min_stick_y: None,
+        };
+        let states = states_with(&[(0, action_states::DASH), (1, action_states::TURN)]);
+        let result = evaluate(&rule, &coords, &coords, &states, None);
+        assert_eq!(result.violations.len(), 1);
+        assert_eq!(
+            result.violations[0].reason,
+            "Dash-turn occurred within 1 frames (Dash at frame 0 to Turn at frame 1)"
+        );
+    }
+}
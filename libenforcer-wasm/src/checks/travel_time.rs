@@ -1,6 +1,12 @@
-use crate::types::{CheckResult, Coord};
+use crate::types::{CheckResult, Coord, FrameUse, StickStream};
 use crate::utils::is_equal_coord;
 
+/// Main stick stream this check reads
+pub const STICK_STREAM: StickStream = StickStream::Processed;
+
+/// Frames this check reads
+pub const FRAMES: FrameUse = FrameUse::Present;
+
 /// Check for illegal travel time patterns on box controllers
 /// Box controllers should have ~36% travel coordinates
 /// Less than 25% indicates suspicious behavior
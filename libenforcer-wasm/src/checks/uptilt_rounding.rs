@@ -1,4 +1,10 @@
-use crate::types::{CheckResult, Coord};
+use crate::types::{CheckResult, Coord, FrameUse, StickStream};
+
+/// Main stick stream this check reads
+pub const STICK_STREAM: StickStream = StickStream::Processed;
+
+/// Frames this check reads
+pub const FRAMES: FrameUse = FrameUse::Present;
 
 /// Check for illegal uptilt rounding on analog controllers
 /// Detects when analog inputs are artificially rounded/quantized
@@ -56,7 +62,7 @@ mod tests {
             Coord { x: 0.1, y: 0.22 },   // In intermediate zone - natural
             Coord { x: 0.0, y: 0.2875 },
         ]);
-        assert_eq!(result.result, false);
+        assert!(!result.result);
     }
 
     #[test]
@@ -70,6 +76,6 @@ mod tests {
             Coord { x: 0.0, y: 0.2875 },
             Coord { x: 0.0, y: 0.2875 },
         ]);
-        assert_eq!(result.result, true);
+        assert!(result.result);
     }
 }
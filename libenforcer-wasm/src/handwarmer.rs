@@ -1,86 +1,147 @@
 use peppi::game::Game;
-use crate::parser::process_analog_stick;
+use crate::outcome::game_outcome;
+use crate::parser::{extract_all_players, finalized_frames, PlayerGameData};
+use crate::types::{
+    FrameStatus, HandwarmerAnalysis, HandwarmerRule, HandwarmerSignals, PortStocks,
+};
 
 /// Deadzone threshold for joystick region classification.
 /// Matches the TypeScript getJoystickRegion() DZ check.
 const DZ_THRESHOLD: f64 = 0.2875;
 
+/// Games shorter than this (1 minute) are handwarmers
+const MIN_GAME_FRAMES: usize = 3600;
+
+/// Longest a player may idle in the deadzone (10 seconds)
+const MAX_DEADZONE_FRAMES: usize = 600;
+
+/// Stage IDs on the tournament stage list: Fountain of Dreams, Pokémon
+/// Stadium, Yoshi's Story, Dream Land, Battlefield, Final Destination
+const TOURNAMENT_STAGES: [u16; 6] = [2, 3, 8, 28, 31, 32];
+const TOURNAMENT_TIMER_SECONDS: u32 = 480;
+const TOURNAMENT_STOCKS: u8 = 4;
+
+/// Frame index 0 is game frame -123
+const FIRST_FRAME: i32 = -123;
+
 fn is_in_deadzone(x: f64, y: f64) -> bool {
     x.abs() < DZ_THRESHOLD && y.abs() < DZ_THRESHOLD
 }
 
-/// Detect whether a game is a handwarmer (warmup/practice session).
-/// Returns true if any player triggers either:
-///   - Game is less than 1 minute (< 3600 frames)
-///   - Player stayed in the deadzone for 10+ consecutive seconds (> 600 frames) while alive
-///
-/// Mirrors TypeScript isHandwarmer() from index.ts.
-pub fn is_handwarmer(game: &impl Game) -> bool {
-    if game.len() == 0 {
-        return true;
+/// First deadzone run of a player longer than MAX_DEADZONE_FRAMES,
+/// as inclusive frame indices
+fn idle_run(data: &PlayerGameData) -> Option<(usize, usize)> {
+    let mut run_start = None;
+
+    for (i, coord) in data.main_coords.iter().enumerate() {
+        // In slippi-js, eliminated players (0 stocks) are excluded from
+        // frame data, resetting the DZ counter. Peppi still includes them,
+        // so absent frames and 0 stocks reset it here.
+        let idle = data.frame_status[i] != FrameStatus::Absent
+            && data.stocks[i] != 0
+            && is_in_deadzone(coord.x, coord.y);
+
+        match (idle, run_start) {
+            (true, None) => run_start = Some(i),
+            (false, Some(start)) => {
+                if i - start > MAX_DEADZONE_FRAMES {
+                    return Some((start, i - 1));
+                }
+                run_start = None;
+            }
+            _ => {}
+        }
     }
 
-    // Get active player ports from game start data.
+    run_start
+        .filter(|&start| data.main_coords.len() - start > MAX_DEADZONE_FRAMES)
+        .map(|start| (start, data.main_coords.len() - 1))
+}
+
+fn signals(game: &impl Game, players: &[(u8, PlayerGameData)], frames: usize) -> HandwarmerSignals {
+    let start = game.start();
+    let outcome = game_outcome(game);
+    let no_damage_dealt = players.iter().all(|(_, data)| {
+        data.percent.iter().all(|&p| p == 0.0)
+            && data.stocks.first().is_none_or(|first| data.stocks.iter().all(|s| s == first))
+    });
+    let tournament_settings = TOURNAMENT_STAGES.contains(&start.stage)
+        && start.timer == TOURNAMENT_TIMER_SECONDS
+        && start.players.iter().all(|p| p.stocks == TOURNAMENT_STOCKS);
+
+    HandwarmerSignals {
+        frames,
+        no_damage_dealt,
+        lras_initiator: outcome.lras_initiator,
+        stage_id: start.stage,
+        timer_seconds: start.timer,
+        tournament_settings,
+        end_stocks: outcome
+            .players
+            .iter()
+            .map(|p| PortStocks {
+                port: p.port,
+                stocks: p.stocks,
+            })
+            .collect(),
+    }
+}
+
+/// Analyze whether a game is a handwarmer (warmup/practice session).
+/// A game is a handwarmer if it triggers either rule:
+///   - Game is less than 1 minute (< 3600 frames)
+///   - A player stayed in the deadzone for 10+ consecutive seconds (> 600 frames) while alive
+///
+/// The first rule triggered is reported with the player and frames responsible,
+/// alongside informational signals such as quit-outs and game settings.
+pub fn analyze(game: &impl Game) -> HandwarmerAnalysis {
     // Uses start().players (real players only), matching TS behavior where
     // game.getFrames()[0].players only includes actual characters.
-    let active_ports: Vec<usize> = game.start()
-        .players
-        .iter()
-        .map(|p| p.port as usize)
-        .collect();
+    analyze_players(game, &extract_all_players(game))
+}
 
+/// `analyze` over player data that has already been extracted
+pub fn analyze_players(game: &impl Game, players: &[(u8, PlayerGameData)]) -> HandwarmerAnalysis {
     // TS uses total game frames for the duration check (coords include
-    // (0,0) entries for frames where the player is absent).
-    let total_frames = game.len();
+    // (0,0) entries for frames where the player is absent). Rolled-back
+    // frame copies don't count towards the game's length.
+    let total_frames = players
+        .first()
+        .map_or_else(|| finalized_frames(game).len(), |(_, data)| data.main_coords.len());
+    let signals = signals(game, players, total_frames);
+    let verdict = |rule, port, range: Option<(usize, usize)>| HandwarmerAnalysis {
+        is_handwarmer: true,
+        rule: Some(rule),
+        port,
+        start_frame: range.map(|(start, _)| start as i32 + FIRST_FRAME),
+        end_frame: range.map(|(_, end)| end as i32 + FIRST_FRAME),
+        signals: signals.clone(),
+    };
 
-    for &port_index in &active_ports {
-        if total_frames < 3600 {
-            return true;
-        }
+    if total_frames < MIN_GAME_FRAMES {
+        let range = (total_frames > 0).then(|| (0, total_frames - 1));
+        return verdict(HandwarmerRule::ShortGame, None, range);
+    }
 
-        let mut deadzone_frames = 0usize;
-
-        for i in 0..total_frames {
-            let frame = game.frame(i);
-
-            match frame.ports.iter().find(|p| p.port as usize == port_index) {
-                Some(port) => {
-                    // In slippi-js, eliminated players (0 stocks) are excluded
-                    // from frame data, resetting the DZ counter. Peppi still
-                    // includes them, so we check stocks explicitly.
-                    if port.leader.post.stocks == 0 {
-                        deadzone_frames = 0;
-                        continue;
-                    }
-
-                    let pre = &port.leader.pre;
-
-                    let coord = if let (Some(rx), Some(ry)) = (pre.raw_analog_x, pre.raw_analog_y) {
-                        process_analog_stick(rx as f32, ry as f32, false)
-                    } else {
-                        crate::types::Coord {
-                            x: pre.joystick.x as f64,
-                            y: pre.joystick.y as f64,
-                        }
-                    };
-
-                    if is_in_deadzone(coord.x, coord.y) {
-                        deadzone_frames += 1;
-                    } else {
-                        deadzone_frames = 0;
-                    }
-
-                    if deadzone_frames > 600 {
-                        return true;
-                    }
-                }
-                None => {
-                    // Player not present in this frame (dead/respawning)
-                    deadzone_frames = 0;
-                }
-            }
+    for (port, data) in players {
+        if let Some(run) = idle_run(data) {
+            return verdict(HandwarmerRule::IdleInDeadzone, Some(*port), Some(run));
         }
     }
 
-    false
+    HandwarmerAnalysis {
+        is_handwarmer: false,
+        rule: None,
+        port: None,
+        start_frame: None,
+        end_frame: None,
+        signals,
+    }
+}
+
+/// Detect whether a game is a handwarmer; see `analyze` for the rules.
+///
+/// Mirrors TypeScript isHandwarmer() from index.ts.
+pub fn is_handwarmer(game: &impl Game) -> bool {
+    analyze(game).is_handwarmer
 }
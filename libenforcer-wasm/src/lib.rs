@@ -12,6 +12,18 @@ pub mod utils;
 pub mod game_timer;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod handwarmer;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod outcome;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod metadata;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod teams;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod analysis;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod ruleset;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod action_states;
 
 // Keep modules private for WASM builds
 #[cfg(target_arch = "wasm32")]
@@ -26,11 +38,27 @@ mod utils;
 mod game_timer;
 #[cfg(target_arch = "wasm32")]
 mod handwarmer;
+#[cfg(target_arch = "wasm32")]
+mod outcome;
+#[cfg(target_arch = "wasm32")]
+mod metadata;
+#[cfg(target_arch = "wasm32")]
+mod teams;
+#[cfg(target_arch = "wasm32")]
+mod analysis;
+#[cfg(target_arch = "wasm32")]
+mod ruleset;
+#[cfg(target_arch = "wasm32")]
+mod action_states;
+
+#[cfg(test)]
+mod test_utils;
 
 use wasm_bindgen::prelude::*;
 use peppi::game::Game;
 use peppi::game::immutable::Game as ImmutableGame;
 use peppi::io::slippi::de::read as read_slippi;
+use serde::Serialize;
 use std::io::Cursor;
 
 #[derive(serde::Serialize)]
@@ -66,6 +94,15 @@ fn coords_from_js(val: JsValue) -> Result<Vec<types::Coord>, JsValue> {
         .map_err(|e| JsValue::from_str(&format!("Failed to deserialize coordinates: {}", e)))
 }
 
+/// Helper to deserialize an optional Ruleset from JsValue (defaults when omitted)
+fn ruleset_from_js(val: Option<JsValue>) -> Result<ruleset::Ruleset, JsValue> {
+    match val {
+        Some(v) if !v.is_undefined() && !v.is_null() => serde_wasm_bindgen::from_value(v)
+            .map_err(|e| JsValue::from_str(&format!("Failed to deserialize ruleset: {}", e))),
+        _ => Ok(ruleset::Ruleset::default()),
+    }
+}
+
 /// Helper to deserialize a single Coord from JsValue
 fn coord_from_js(val: JsValue) -> Result<types::Coord, JsValue> {
     serde_wasm_bindgen::from_value(val)
@@ -109,6 +146,14 @@ pub fn get_joystick_region(x: f64, y: f64) -> u8 {
     utils::get_joystick_region(x, y) as u8
 }
 
+/// Name of an action state (e.g. "SquatWait"), or its hex ID if unknown.
+/// Common states are always named; character-specific states are only named
+/// for Fox and Falco, given the character ID from game start.
+#[wasm_bindgen]
+pub fn get_action_state_name(state: u16, character: Option<u8>) -> String {
+    action_states::display_name(state, character)
+}
+
 /// Process raw analog stick values into normalized coordinates
 #[wasm_bindgen]
 pub fn process_analog_stick(x: f64, y: f64, deadzone: bool) -> Result<JsValue, JsValue> {
@@ -149,6 +194,17 @@ pub fn get_target_coords(coords: JsValue) -> Result<JsValue, JsValue> {
         .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
 }
 
+/// Cluster held coordinates into modifier notches (no whitelist applied).
+/// Coordinates are taken as one per frame from game frame -123.
+#[wasm_bindgen]
+pub fn get_coord_inventory(coords: JsValue) -> Result<JsValue, JsValue> {
+    let coords = coords_from_js(coords)?;
+    let frame_numbers: Vec<i32> = (0..coords.len() as i32).map(|i| i - 123).collect(); // Frames start at -123
+    let notches = checks::coord_inventory::cluster_notches(&coords, &frame_numbers);
+    serde_wasm_bindgen::to_value(&notches)
+        .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+}
+
 // ---- SlpGame: parse once, query many times ----
 
 /// A parsed SLP game that can be queried without re-parsing.
@@ -172,21 +228,69 @@ impl SlpGame {
 
     /// Run all applicable checks on a player and return structured results.
     /// Controller type is detected once; only relevant checks are run.
+    /// An optional ruleset overrides the default rule parameters.
     #[wasm_bindgen(js_name = "analyzePlayer")]
-    pub fn analyze_player(&self, player_index: usize) -> Result<JsValue, JsValue> {
+    pub fn analyze_player(&self, player_index: usize, ruleset: Option<JsValue>) -> Result<JsValue, JsValue> {
+        let ruleset = ruleset_from_js(ruleset)?;
         let player_data = parser::extract_player_data(&self.game, player_index)
             .ok_or_else(|| JsValue::from_str("Player not found in this game"))?;
-        let results = checks::analyze_player(&player_data);
+        let results = checks::analyze_player_with_ruleset(&player_data, &ruleset);
         serde_wasm_bindgen::to_value(&results)
             .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
     }
 
-    /// Extract main stick coordinates for a player
+    /// Analyze every present player in one pass over the frames, returning
+    /// each player's analysis by port alongside the handwarmer verdict, game
+    /// outcome and team summaries. An optional ruleset overrides the default
+    /// rule parameters.
+    #[wasm_bindgen(js_name = "analyzeAll")]
+    pub fn analyze_all(&self, ruleset: Option<JsValue>) -> Result<JsValue, JsValue> {
+        let ruleset = ruleset_from_js(ruleset)?;
+        // Plain object keyed by port rather than a JS Map
+        let serializer = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+        analysis::analyze_game(&self.game, &ruleset)
+            .serialize(&serializer)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Analyze every player and summarize the verdicts per team. Empty for
+    /// games without teams. An optional ruleset overrides the default rule parameters.
+    #[wasm_bindgen(js_name = "analyzeTeams")]
+    pub fn analyze_teams(&self, ruleset: Option<JsValue>) -> Result<JsValue, JsValue> {
+        let ruleset = ruleset_from_js(ruleset)?;
+        serde_wasm_bindgen::to_value(&analysis::analyze_game(&self.game, &ruleset).teams)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Extract main stick coordinates for a player on the frames they're present,
+    /// the coordinates controller classification reads
     #[wasm_bindgen(js_name = "getMainStickCoords")]
     pub fn get_main_stick_coords(&self, player_index: usize) -> Result<JsValue, JsValue> {
         let player_data = parser::extract_player_data(&self.game, player_index)
             .ok_or_else(|| JsValue::from_str("Player not found"))?;
-        serde_wasm_bindgen::to_value(&player_data.main_coords)
+        let coords = player_data.stick_frames(types::StickStream::Processed, types::FrameUse::Present);
+        serde_wasm_bindgen::to_value(&coords)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Main stick data for a player in all three streams: raw hardware values,
+    /// the game engine's processed values and this crate's processed coordinates,
+    /// each flagged when it had to be approximated from another stream
+    #[wasm_bindgen(js_name = "getStickStreams")]
+    pub fn get_stick_streams(&self, player_index: usize) -> Result<JsValue, JsValue> {
+        let player_data = parser::extract_player_data(&self.game, player_index)
+            .ok_or_else(|| JsValue::from_str("Player not found"))?;
+        serde_wasm_bindgen::to_value(&player_data.stick_streams())
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Per-frame status of a player (Active, Absent, Dead or RespawnInvincible),
+    /// aligned with the per-frame coordinate arrays
+    #[wasm_bindgen(js_name = "getFrameStatus")]
+    pub fn get_frame_status(&self, player_index: usize) -> Result<JsValue, JsValue> {
+        let player_data = parser::extract_player_data(&self.game, player_index)
+            .ok_or_else(|| JsValue::from_str("Player not found"))?;
+        serde_wasm_bindgen::to_value(&player_data.frame_status)
             .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
     }
 
@@ -204,7 +308,28 @@ impl SlpGame {
     pub fn is_box_controller(&self, player_index: usize) -> Result<bool, JsValue> {
         let player_data = parser::extract_player_data(&self.game, player_index)
             .ok_or_else(|| JsValue::from_str("Player not found"))?;
-        Ok(utils::is_box_controller(&player_data.main_coords))
+        Ok(utils::is_box_controller(
+            &player_data.stick_frames(types::StickStream::Processed, types::FrameUse::Present),
+        ))
+    }
+
+    /// Per-frame SDI and joystick regions for a player over an inclusive range
+    /// of game frames (whole game when omitted), with the SDI events seen on
+    /// each frame: hitlag start/end, SDI inputs and rule violations.
+    #[wasm_bindgen(js_name = "getRegionTrace")]
+    pub fn get_region_trace(
+        &self,
+        player_index: usize,
+        start_frame: Option<i32>,
+        end_frame: Option<i32>,
+        ruleset: Option<JsValue>,
+    ) -> Result<JsValue, JsValue> {
+        let ruleset = ruleset_from_js(ruleset)?;
+        let player_data = parser::extract_player_data(&self.game, player_index)
+            .ok_or_else(|| JsValue::from_str("Player not found"))?;
+        let trace = checks::sdi::trace(&player_data, &ruleset, start_frame, end_frame);
+        serde_wasm_bindgen::to_value(&trace)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
     }
 
     /// Check if the game is a handwarmer
@@ -213,6 +338,30 @@ impl SlpGame {
         handwarmer::is_handwarmer(&self.game)
     }
 
+    /// Handwarmer verdict with the rule, player and frames that triggered it,
+    /// plus signals like quit-outs, damage dealt and game settings
+    #[wasm_bindgen(js_name = "getHandwarmerAnalysis")]
+    pub fn get_handwarmer_analysis(&self) -> Result<JsValue, JsValue> {
+        serde_wasm_bindgen::to_value(&handwarmer::analyze(&self.game))
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// How the game ended: end method, quit-out initiator, and each port's
+    /// placement, stocks and percent
+    #[wasm_bindgen(js_name = "getGameOutcome")]
+    pub fn get_game_outcome(&self) -> Result<JsValue, JsValue> {
+        serde_wasm_bindgen::to_value(&outcome::game_outcome(&self.game))
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Full game metadata: start time, platform, netplay identities, team mode,
+    /// timer, game mode, versions and Slippi Online match info
+    #[wasm_bindgen(js_name = "getMetadata")]
+    pub fn get_metadata(&self) -> Result<JsValue, JsValue> {
+        serde_wasm_bindgen::to_value(&metadata::game_metadata(&self.game))
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
     /// Extract game settings (stage, players)
     #[wasm_bindgen(js_name = "getGameSettings")]
     pub fn get_game_settings(&self) -> Result<JsValue, JsValue> {
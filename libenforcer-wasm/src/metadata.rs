@@ -0,0 +1,96 @@
+use peppi::game::shift_jis::MeleeString;
+use peppi::game::Game;
+use crate::game_timer::TimerType;
+use crate::types::{GameMetadata, GameMode, PlayerMetadata};
+
+/// Scene major number while playing over Slippi Online
+const ONLINE_SCENE_MAJOR: u8 = 8;
+
+/// Slippi Online match id prefixes, e.g. "mode.unranked-2024-12-29T01:55:47.78-1"
+const MATCH_MODES: [(&str, GameMode); 4] = [
+    ("mode.ranked", GameMode::OnlineRanked),
+    ("mode.unranked", GameMode::OnlineUnranked),
+    ("mode.direct", GameMode::OnlineDirect),
+    ("mode.teams", GameMode::OnlineTeams),
+];
+
+fn timer_type(bitfield: u8) -> TimerType {
+    match bitfield & 0b11 {
+        2 => TimerType::Decreasing,
+        3 => TimerType::Increasing,
+        _ => TimerType::None,
+    }
+}
+
+/// A string from the metadata block, if present
+fn metadata_str(game: &impl Game, path: &[&str]) -> Option<String> {
+    let (last, parents) = path.split_last()?;
+    let mut map = game.metadata().as_ref()?;
+    for key in parents {
+        map = map.get(*key)?.as_object()?;
+    }
+    map.get(*last)?.as_str().map(str::to_string)
+}
+
+/// Normalized start block string, or None if empty. Shift JIS names use
+/// full-width characters (e.g. "CARV＃889"), which normalizing maps back.
+fn melee_str(s: &MeleeString) -> Option<String> {
+    Some(s.to_normalized()).filter(|s| !s.is_empty())
+}
+
+/// Collect the game's metadata from the start block and metadata block
+pub fn game_metadata(game: &impl Game) -> GameMetadata {
+    let start = game.start();
+    let match_info = start.r#match.as_ref().filter(|m| !m.id.is_empty());
+
+    let game_mode = match_info
+        .and_then(|m| MATCH_MODES.iter().find(|(prefix, _)| m.id.starts_with(prefix)))
+        .map(|&(_, mode)| mode)
+        .unwrap_or(match start.scene {
+            Some(scene) if scene.major == ONLINE_SCENE_MAJOR => GameMode::Online,
+            _ => GameMode::Offline,
+        });
+
+    let players = start
+        .players
+        .iter()
+        .map(|p| {
+            let netplay = p.netplay.as_ref();
+            let port = (p.port as u8).to_string();
+            // The metadata block has plain ASCII names; fall back to the start block's
+            let name = |key| {
+                metadata_str(game, &["players", &port, "names", key]).filter(|s| !s.is_empty())
+            };
+            PlayerMetadata {
+                port: p.port as u8,
+                character_id: p.character,
+                player_type: p.r#type as u8,
+                character_color: p.costume,
+                team: p.team.map(|t| t.color),
+                stocks: p.stocks,
+                name_tag: p.name_tag.as_ref().and_then(melee_str),
+                netplay_name: name("netplay").or_else(|| netplay.and_then(|n| melee_str(&n.name))),
+                connect_code: name("code").or_else(|| netplay.and_then(|n| melee_str(&n.code))),
+                slippi_uid: netplay.and_then(|n| n.suid.clone()).filter(|s| !s.is_empty()),
+            }
+        })
+        .collect();
+
+    GameMetadata {
+        start_at: metadata_str(game, &["startAt"]),
+        played_on: metadata_str(game, &["playedOn"]),
+        console_nick: metadata_str(game, &["consoleNick"]),
+        slippi_version: start.slippi.version.to_string(),
+        is_pal: start.is_pal,
+        is_frozen_ps: start.is_frozen_ps,
+        game_mode,
+        is_teams: start.is_teams,
+        stage_id: start.stage,
+        timer_type: timer_type(start.bitfield[0]),
+        timer_seconds: start.timer,
+        match_id: match_info.map(|m| m.id.clone()),
+        game_number: match_info.map(|m| m.game),
+        tiebreaker_number: match_info.map(|m| m.tiebreaker),
+        players,
+    }
+}
@@ -0,0 +1,47 @@
+use peppi::game::{EndMethod, Game};
+use crate::types::{GameEndMethod, GameOutcome, PlayerOutcome};
+
+fn end_method(method: EndMethod) -> GameEndMethod {
+    match method {
+        EndMethod::Unresolved => GameEndMethod::Unresolved,
+        EndMethod::Time => GameEndMethod::Time,
+        EndMethod::Game => GameEndMethod::Game,
+        EndMethod::Resolved => GameEndMethod::Resolved,
+        EndMethod::NoContest => GameEndMethod::NoContest,
+    }
+}
+
+/// Read how a game ended from its end block, and each port's stocks and
+/// percent from the last finalized frame
+pub fn game_outcome(game: &impl Game) -> GameOutcome {
+    let end = game.end().as_ref();
+    // The last copy of a frame id is the finalized one, so the last frame always is
+    let last_frame = game.len().checked_sub(1).map(|i| game.frame(i));
+
+    let players = game
+        .start()
+        .players
+        .iter()
+        .map(|player| {
+            let post = last_frame
+                .as_ref()
+                .and_then(|frame| frame.ports.iter().find(|p| p.port == player.port))
+                .map(|port| &port.leader.post);
+            PlayerOutcome {
+                port: player.port as u8,
+                placement: end
+                    .and_then(|end| end.players.as_ref())
+                    .and_then(|players| players.iter().find(|p| p.port == player.port))
+                    .map(|p| p.placement),
+                stocks: post.map_or(player.stocks, |post| post.stocks),
+                percent: post.map_or(0.0, |post| post.percent),
+            }
+        })
+        .collect();
+
+    GameOutcome {
+        end_method: end.map(|end| end_method(end.method)),
+        lras_initiator: end.and_then(|end| end.lras_initiator.flatten()).map(|port| port as u8),
+        players,
+    }
+}
@@ -1,77 +1,321 @@
-use crate::types::Coord;
+use crate::action_states::{DEAD_DOWN, REBIRTH, REBIRTH_WAIT, SLEEP};
+use crate::types::{Coord, FrameStatus, FrameUse, StickStream, StickStreamData, StickStreams};
+use std::borrow::Cow;
+use peppi::frame::transpose::PortData;
 use peppi::game::Game;
+use std::collections::HashSet;
 
-/// Extracted game data for a single player
+/// Hurtbox state while invulnerable (respawn invincibility)
+const HURTBOX_INVULNERABLE: u8 = 1;
+
+/// Extracted game data for a single player. Every per-frame vector has one
+/// entry per finalized frame, so index i is game frame i - 123.
 pub struct PlayerGameData {
-    pub main_coords: Vec<Coord>,       // Main joystick coordinates
+    pub main_coords: Vec<Coord>,       // Main joystick coordinates, processed by this crate
     pub c_coords: Vec<Coord>,          // C-stick coordinates
     pub action_states: Vec<u16>,       // Action state IDs per frame
-    pub raw_joystick_coords: Vec<Coord>, // Raw joystick values for uptilt check
+    pub raw_joystick_coords: Vec<Coord>, // Raw int8 joystick values
+    pub engine_coords: Vec<Coord>,     // Game engine's processed joystick (pre.joystick)
+    pub raw_x_approximated: bool,      // Raw X rebuilt from engine values on some frame (pre Slippi v1.2)
+    pub raw_y_approximated: bool,      // Raw Y rebuilt from engine values on some frame (pre Slippi v3.15)
+    pub character: Option<u8>,         // Character ID from game start, for naming action states
+    pub team: Option<u8>,              // Team color from game start (team games only)
+    pub hitlag: Option<Vec<f32>>,      // Hitlag frames remaining per frame (None before Slippi v3.8)
+    pub positions: Vec<Coord>,         // Character position (game units) after each frame
+    pub percent: Vec<f32>,             // Damage percent after each frame
+    pub stocks: Vec<u8>,               // Stocks remaining after each frame
+    pub l_trigger: Vec<f64>,           // Physical L trigger (0.0-1.0, 1/140 steps)
+    pub r_trigger: Vec<f64>,           // Physical R trigger (0.0-1.0, 1/140 steps)
+    pub slippi_version: String,        // Replay format version, e.g. "3.16.0"
+    pub missing_fields: Vec<String>,   // Replay fields absent on some frame (e.g. "raw_analog_y")
+    pub rollback_frames: usize,        // Rolled-back frame copies discarded before extraction
+    pub frame_status: Vec<FrameStatus>, // Whether the player was in play on each frame
 }
 
-/// Extract all relevant data for analysis from a Peppi Game
-pub fn extract_player_data(game: &impl Game, player_index: usize) -> Option<PlayerGameData> {
-    let mut main_coords = Vec::new();
-    let mut c_coords = Vec::new();
-    let mut action_states = Vec::new();
-    let mut raw_joystick_coords = Vec::new();
+impl PlayerGameData {
+    /// Main stick coordinates in the given stream
+    pub fn stick(&self, stream: StickStream) -> &[Coord] {
+        match stream {
+            StickStream::Raw => &self.raw_joystick_coords,
+            StickStream::Engine => &self.engine_coords,
+            StickStream::Processed => &self.main_coords,
+        }
+    }
 
-    // Iterate through all frames using the Game trait
-    for i in 0..game.len() {
-        let frame = game.frame(i);
+    /// Whether each axis (x, y) of a stream had to be approximated. Processed
+    /// coordinates are derived from raw values, so they're approximated
+    /// whenever those are.
+    pub fn approximated_axes(&self, stream: StickStream) -> (bool, bool) {
+        match stream {
+            StickStream::Raw | StickStream::Processed => (self.raw_x_approximated, self.raw_y_approximated),
+            StickStream::Engine => (false, false),
+        }
+    }
+
+    /// Whether either axis of a stream had to be approximated
+    pub fn is_approximated(&self, stream: StickStream) -> bool {
+        let (x, y) = self.approximated_axes(stream);
+        x || y
+    }
+
+    /// Values of a per-frame vector restricted to the frames a check reads
+    pub fn frames<'a, T: Clone>(&self, values: &'a [T], frames: FrameUse) -> Cow<'a, [T]> {
+        match frames {
+            FrameUse::Aligned => Cow::Borrowed(values),
+            FrameUse::Present => Cow::Owned(
+                values
+                    .iter()
+                    .zip(&self.frame_status)
+                    .filter(|(_, status)| **status != FrameStatus::Absent)
+                    .map(|(value, _)| value.clone())
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Game frame number of each value `frames` keeps, so checks over
+    /// present frames can report frames a TO can find in the replay
+    pub fn frame_numbers(&self, frames: FrameUse) -> Vec<i32> {
+        let numbers: Vec<i32> = (0..self.frame_status.len() as i32).map(|i| i - 123).collect(); // Frames start at -123
+        self.frames(&numbers, frames).into_owned()
+    }
+
+    /// Main stick coordinates in the given stream, over the frames a check reads
+    pub fn stick_frames(&self, stream: StickStream, frames: FrameUse) -> Cow<'_, [Coord]> {
+        self.frames(self.stick(stream), frames)
+    }
+
+    /// All three main stick streams, with their approximated flags
+    pub fn stick_streams(&self) -> StickStreams {
+        let data = |stream| {
+            let (approximated_x, approximated_y) = self.approximated_axes(stream);
+            StickStreamData {
+                coords: self.stick(stream).to_vec(),
+                approximated_x,
+                approximated_y,
+            }
+        };
+        StickStreams {
+            raw: data(StickStream::Raw),
+            engine: data(StickStream::Engine),
+            processed: data(StickStream::Processed),
+        }
+    }
+}
+
+/// Indices of the finalized frames, in order. Netplay replays record a frame
+/// id again each time a rollback re-simulates it; only the last copy is final.
+pub fn finalized_frames(game: &impl Game) -> Vec<usize> {
+    let mut seen = HashSet::new();
+    let mut frames: Vec<usize> = (0..game.len())
+        .rev()
+        .filter(|&i| seen.insert(game.frame(i).id))
+        .collect();
+    frames.reverse();
+    frames
+}
+
+/// Raw value of one main stick axis, approximated from the engine-normalized
+/// joystick when the replay doesn't record it. Returns (value, approximated).
+fn raw_axis(raw: Option<i8>, engine: f32) -> (f32, bool) {
+    match raw {
+        Some(value) => (value as f32, false),
+        None => (engine * 80.0, true),
+    }
+}
+
+/// Per-frame vectors of one player, filled one frame at a time
+#[derive(Default)]
+struct PlayerFrames {
+    main_coords: Vec<Coord>,
+    c_coords: Vec<Coord>,
+    action_states: Vec<u16>,
+    raw_joystick_coords: Vec<Coord>,
+    engine_coords: Vec<Coord>,
+    raw_x_approximated: bool,
+    raw_y_approximated: bool,
+    hitlag: Vec<Option<f32>>,
+    positions: Vec<Coord>,
+    percent: Vec<f32>,
+    stocks: Vec<u8>,
+    l_trigger: Vec<f64>,
+    r_trigger: Vec<f64>,
+    frame_status: Vec<FrameStatus>,
+    missing_fields: Vec<String>,
+}
+
+impl PlayerFrames {
+    fn note_missing(&mut self, field: &str) {
+        if !self.missing_fields.iter().any(|f| f == field) {
+            self.missing_fields.push(field.to_string());
+        }
+    }
 
-        // Find the port data for this player
-        let port_data = match frame.ports.iter().find(|p| p.port as usize == player_index) {
-            Some(port) => port,
-            None => continue, // Player doesn't exist in this frame
+    /// Append one frame. Frames without this player's port data keep their
+    /// place with neutral placeholders so indices stay aligned.
+    fn push(&mut self, port_data: Option<&PortData>) {
+        let Some(port_data) = port_data else {
+            let origin = Coord { x: 0.0, y: 0.0 };
+            self.main_coords.push(origin);
+            self.engine_coords.push(origin);
+            self.raw_joystick_coords.push(origin);
+            self.c_coords.push(origin);
+            self.l_trigger.push(0.0);
+            self.r_trigger.push(0.0);
+            self.action_states.push(SLEEP);
+            self.hitlag.push(Some(0.0));
+            self.positions.push(self.positions.last().copied().unwrap_or(origin));
+            self.percent.push(self.percent.last().copied().unwrap_or(0.0));
+            self.stocks.push(self.stocks.last().copied().unwrap_or(0));
+            self.frame_status.push(FrameStatus::Absent);
+            return;
         };
 
         // Extract pre-frame data (inputs before processing)
         let pre = &port_data.leader.pre;
         let post = &port_data.leader.post;
 
-        // Main stick - use raw analog values (int8, like slippi-js rawJoystickX/Y)
-        // when available, otherwise fall back to peppi's already-normalized joystick
-        // (raw_analog_x added in Slippi v1.2, raw_analog_y in v3.15)
-        let (processed_main, raw_x_f64, raw_y_f64) =
-            if let (Some(rx), Some(ry)) = (pre.raw_analog_x, pre.raw_analog_y) {
-                let rx_f32 = rx as f32;
-                let ry_f32 = ry as f32;
-                (process_analog_stick(rx_f32, ry_f32, false), rx_f32 as f64, ry_f32 as f64)
-            } else {
-                // Fallback: peppi's joystick values are already engine-normalized (-1..1)
-                let coord = Coord { x: pre.joystick.x as f64, y: pre.joystick.y as f64 };
-                // Raw values unavailable, approximate from normalized
-                (coord, pre.joystick.x as f64 * 80.0, pre.joystick.y as f64 * 80.0)
-            };
-        main_coords.push(processed_main);
-
-        // Raw joystick for uptilt check
-        raw_joystick_coords.push(Coord {
-            x: raw_x_f64,
-            y: raw_y_f64,
+        // Main stick - use raw analog values (int8, like slippi-js rawJoystickX/Y).
+        // An axis the replay doesn't record is approximated from peppi's
+        // engine-normalized joystick (raw_analog_x added in Slippi v1.2, raw_analog_y in v3.15)
+        let (raw_x, x_approximated) = raw_axis(pre.raw_analog_x, pre.joystick.x);
+        let (raw_y, y_approximated) = raw_axis(pre.raw_analog_y, pre.joystick.y);
+        if x_approximated {
+            self.raw_x_approximated = true;
+            self.note_missing("raw_analog_x");
+        }
+        if y_approximated {
+            self.raw_y_approximated = true;
+            self.note_missing("raw_analog_y");
+        }
+        self.main_coords.push(process_analog_stick(raw_x, raw_y, false));
+        self.engine_coords.push(Coord {
+            x: pre.joystick.x as f64,
+            y: pre.joystick.y as f64,
+        });
+
+        // Raw joystick values
+        self.raw_joystick_coords.push(Coord {
+            x: raw_x as f64,
+            y: raw_y as f64,
         });
 
         // C-stick - already normalized in Peppi
-        c_coords.push(Coord {
+        self.c_coords.push(Coord {
             x: pre.cstick.x as f64,
             y: pre.cstick.y as f64,
         });
 
+        self.l_trigger.push(pre.triggers_physical.l as f64);
+        self.r_trigger.push(pre.triggers_physical.r as f64);
+
         // Extract post-frame data (game state after processing)
-        action_states.push(post.state);
+        self.action_states.push(post.state);
+        if post.hitlag.is_none() {
+            self.note_missing("hitlag");
+        }
+        self.hitlag.push(post.hitlag);
+        self.positions.push(Coord {
+            x: post.position.x as f64,
+            y: post.position.y as f64,
+        });
+        self.percent.push(post.percent);
+        self.stocks.push(post.stocks);
+
+        // Respawn invincibility lasts from the platform until the hurtbox
+        // first turns vulnerable again
+        let after_respawn = self.frame_status.last() == Some(&FrameStatus::RespawnInvincible)
+            && post.hurtbox_state == Some(HURTBOX_INVULNERABLE);
+        self.frame_status.push(if post.stocks == 0 || (DEAD_DOWN..=SLEEP).contains(&post.state) {
+            FrameStatus::Dead
+        } else if post.state == REBIRTH || post.state == REBIRTH_WAIT || after_respawn {
+            FrameStatus::RespawnInvincible
+        } else {
+            FrameStatus::Active
+        });
+    }
+
+    /// None if the player never appeared
+    fn finish(
+        self,
+        game: &impl Game,
+        player_index: usize,
+        rollback_frames: usize,
+    ) -> Option<PlayerGameData> {
+        if self.frame_status.iter().all(|s| *s == FrameStatus::Absent) {
+            return None;
+        }
+
+        let player = game
+            .start()
+            .players
+            .iter()
+            .find(|p| p.port as usize == player_index);
+
+        Some(PlayerGameData {
+            main_coords: self.main_coords,
+            c_coords: self.c_coords,
+            action_states: self.action_states,
+            raw_joystick_coords: self.raw_joystick_coords,
+            engine_coords: self.engine_coords,
+            raw_x_approximated: self.raw_x_approximated,
+            raw_y_approximated: self.raw_y_approximated,
+            character: player.map(|p| p.character),
+            team: player.and_then(|p| p.team).map(|t| t.color),
+            hitlag: self.hitlag.into_iter().collect(),
+            positions: self.positions,
+            percent: self.percent,
+            stocks: self.stocks,
+            l_trigger: self.l_trigger,
+            r_trigger: self.r_trigger,
+            slippi_version: game.start().slippi.version.to_string(),
+            missing_fields: self.missing_fields,
+            rollback_frames,
+            frame_status: self.frame_status,
+        })
     }
+}
+
+/// Extract analysis data for several players in a single pass over the finalized frames.
+/// Players that never appear are left out.
+/// Rolled-back frames are skipped so consecutive entries are consecutive frames.
+pub fn extract_players(game: &impl Game, player_indices: &[usize]) -> Vec<(usize, PlayerGameData)> {
+    let mut players: Vec<PlayerFrames> = player_indices.iter().map(|_| PlayerFrames::default()).collect();
+
+    let frames = finalized_frames(game);
+    let rollback_frames = game.len() - frames.len();
 
-    if main_coords.is_empty() {
-        return None;
+    for i in frames {
+        let frame = game.frame(i);
+        for (&player_index, player) in player_indices.iter().zip(players.iter_mut()) {
+            player.push(frame.ports.iter().find(|p| p.port as usize == player_index));
+        }
     }
 
-    Some(PlayerGameData {
-        main_coords,
-        c_coords,
-        action_states,
-        raw_joystick_coords,
-    })
+    player_indices
+        .iter()
+        .zip(players)
+        .filter_map(|(&player_index, player)| {
+            player
+                .finish(game, player_index, rollback_frames)
+                .map(|data| (player_index, data))
+        })
+        .collect()
+}
+
+/// Extract analysis data for every player in the game start block
+pub fn extract_all_players(game: &impl Game) -> Vec<(u8, PlayerGameData)> {
+    let ports: Vec<usize> = game.start().players.iter().map(|p| p.port as usize).collect();
+    extract_players(game, &ports)
+        .into_iter()
+        .map(|(port, data)| (port as u8, data))
+        .collect()
+}
+
+/// Extract all relevant data for analysis from a Peppi Game.
+/// Rolled-back frames are skipped so consecutive entries are consecutive frames.
+pub fn extract_player_data(game: &impl Game, player_index: usize) -> Option<PlayerGameData> {
+    extract_players(game, &[player_index]).pop().map(|(_, data)| data)
 }
 
 /// Process analog stick values to match the TypeScript implementation
@@ -127,6 +371,17 @@ pub fn process_analog_stick(x: f32, y: f32, deadzone: bool) -> Coord {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_raw_axis_keeps_recorded_values() {
+        // Replays from v1.2 to before v3.15 record raw X but not raw Y
+        assert_eq!(raw_axis(Some(-37), -0.4625), (-37.0, false));
+        assert_eq!(raw_axis(None, 0.6625), (53.0, true));
+
+        let coord = process_analog_stick(raw_axis(Some(-37), -0.4625).0, raw_axis(None, 0.6625).0, false);
+        assert_eq!(coord.x, -37.0 / 80.0);
+        assert_eq!(coord.y, 53.0 / 80.0);
+    }
+
     #[test]
     fn test_process_analog_stick_zero() {
         let coord = process_analog_stick(0.0, 0.0, false);
@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+
+use crate::checks::transitions;
+use crate::types::{Coord, RegionGeometry, SnapbackFilter, SocdMode, StickRegion, TransitionRule};
+
+/// Tournament ruleset parameters used by the checks.
+/// Every field has a default matching the current Melee controller ruleset,
+/// so callers only need to supply the fields they want to override.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Ruleset {
+    /// SOCD resolution modes allowed on the left/right axis
+    pub allowed_socd_horizontal: Vec<SocdMode>,
+    /// SOCD resolution modes allowed on the up/down axis
+    pub allowed_socd_vertical: Vec<SocdMode>,
+    /// Whether a box's inferred SOCD mode must be one of the allowed modes.
+    /// Off by default until mode inference is calibrated on more replays.
+    pub require_socd_mode: bool,
+    /// Main stick coordinates a box may output, compared by absolute value
+    /// so one entry covers all four quadrants. None skips the whitelist check.
+    pub allowed_coordinates: Option<Vec<Coord>>,
+    /// Most firmware-remapped notches an analog controller may have. None means no limit.
+    pub max_remapped_notches: Option<usize>,
+    /// Highest snapping score an analog stick axis may reach. None means no limit.
+    pub max_snapping_score: Option<f64>,
+    /// Never-output raw X/Y values (over both axes) before an analog curve is
+    /// flagged as remapped. Native sticks show at most one or two isolated gaps per axis.
+    pub min_remap_gap_values: usize,
+    /// Share of judged raw X/Y values that must be gaps before a curve is flagged
+    pub min_remap_gap_fraction: f64,
+    /// Raw stick regions an analog controller may not output, compared by
+    /// absolute value. Empty by default.
+    pub forbidden_stick_regions: Vec<StickRegion>,
+    /// Snapback filter strengths allowed on analog sticks
+    pub allowed_snapback_filters: Vec<SnapbackFilter>,
+    /// Minimum action state transition times, each reported separately
+    pub transition_rules: Vec<TransitionRule>,
+    /// C-stick axis values a box may not output, compared by absolute value
+    pub forbidden_cstick_x: Vec<f64>,
+    pub forbidden_cstick_y: Vec<f64>,
+    /// Angles (degrees) angled C-stick outputs may sit at, folded into one
+    /// quadrant. Cardinals are always allowed. None skips the angle check.
+    pub allowed_cstick_angles: Option<Vec<f64>>,
+    /// Lowest C-stick travel coordinate hit rate. None means no minimum.
+    pub min_cstick_travel_rate: Option<f64>,
+    /// Whether a box's analog triggers must be fuzzed. Off by default since
+    /// box lightshield values are not fuzzed by current firmwares.
+    pub require_trigger_fuzzing: bool,
+    /// Report every SDI rule match instead of one violation per incident
+    pub sdi_raw_violations: bool,
+    /// Whether hits displaced further than legal SDI allows fail the player.
+    /// Off by default: the displacement bound is a rough model, so outliers
+    /// are reported for review rather than disqualifying.
+    pub require_sdi_distance: bool,
+    /// Stick thresholds for the deadzone, tilt zone and SDI directions
+    pub sdi_regions: RegionGeometry,
+    /// Let checks that ran on approximated or incomplete data (older replays)
+    /// fail the player. Off by default so old replays never get a false verdict.
+    pub fail_on_degraded_data: bool,
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        Ruleset {
+            allowed_socd_horizontal: vec![SocdMode::SecondInputPriority, SocdMode::Neutral],
+            allowed_socd_vertical: vec![SocdMode::SecondInputPriority, SocdMode::Neutral],
+            require_socd_mode: false,
+            allowed_coordinates: None,
+            max_remapped_notches: None,
+            max_snapping_score: None,
+            min_remap_gap_values: 8,
+            min_remap_gap_fraction: 0.05,
+            forbidden_stick_regions: vec![],
+            allowed_snapback_filters: vec![
+                SnapbackFilter::Unfiltered,
+                SnapbackFilter::Light,
+                SnapbackFilter::Aggressive,
+            ],
+            transition_rules: transitions::default_rules(),
+            forbidden_cstick_x: vec![0.8, 0.6625],
+            forbidden_cstick_y: vec![0.8, 0.6625],
+            allowed_cstick_angles: None,
+            min_cstick_travel_rate: None,
+            require_trigger_fuzzing: false,
+            sdi_raw_violations: false,
+            require_sdi_distance: false,
+            sdi_regions: RegionGeometry::SDI,
+            fail_on_degraded_data: false,
+        }
+    }
+}
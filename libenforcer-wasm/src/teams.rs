@@ -0,0 +1,35 @@
+use crate::types::{PlayerAnalysis, TeamMember, TeamSummary};
+
+/// Summarize one game's player analyses per team, in team color order.
+/// Players without a team (singles) are left out.
+///
+/// Members are identified by port, which only names a player within a game,
+/// so summaries from different games can't be merged by port.
+pub fn summarize(players: &[(u8, &PlayerAnalysis)]) -> Vec<TeamSummary> {
+    let mut teams: Vec<u8> = players.iter().filter_map(|(_, analysis)| analysis.team).collect();
+    teams.sort_unstable();
+    teams.dedup();
+
+    teams
+        .into_iter()
+        .map(|team| {
+            let members: Vec<TeamMember> = players
+                .iter()
+                .filter(|(_, analysis)| analysis.team == Some(team))
+                .map(|(port, analysis)| TeamMember {
+                    port: *port,
+                    controller_type: analysis.controller_type,
+                    is_legal: analysis.is_legal,
+                })
+                .collect();
+            TeamSummary {
+                team,
+                is_legal: members.iter().all(|m| m.is_legal),
+                mixed_controller_types: members
+                    .iter()
+                    .any(|m| m.controller_type != members[0].controller_type),
+                members,
+            }
+        })
+        .collect()
+}
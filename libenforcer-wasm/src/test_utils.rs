@@ -0,0 +1,53 @@
+//! Input sequences and player data shared by unit tests
+
+use crate::parser::PlayerGameData;
+use crate::types::{Coord, FrameStatus};
+
+/// Player data from a modern replay that records everything, with the given
+/// main stick coordinates and every other input at neutral
+pub fn player_data(coords: Vec<Coord>) -> PlayerGameData {
+    let n = coords.len();
+    let origin = Coord::new(0.0, 0.0);
+    PlayerGameData {
+        main_coords: coords.clone(),
+        c_coords: vec![origin; n],
+        action_states: vec![0; n],
+        raw_joystick_coords: coords.iter().map(|c| Coord::new(c.x * 80.0, c.y * 80.0)).collect(),
+        engine_coords: coords,
+        raw_x_approximated: false,
+        raw_y_approximated: false,
+        character: None,
+        team: None,
+        hitlag: Some(vec![0.0; n]),
+        positions: vec![origin; n],
+        percent: vec![0.0; n],
+        stocks: vec![4; n],
+        l_trigger: vec![0.0; n],
+        r_trigger: vec![0.0; n],
+        slippi_version: "3.16.0".to_string(),
+        missing_fields: vec![],
+        rollback_frames: 0,
+        frame_status: vec![FrameStatus::Active; n],
+    }
+}
+
+/// Game frame numbers for `len` frames starting at frame -123
+pub fn game_frames(len: usize) -> Vec<i32> {
+    (0..len as i32).map(|i| i - 123).collect()
+}
+
+/// Helper: create a coordinate sequence simulating repeated targeting events.
+/// Each event: neutral hold → travel frame → output hold
+pub fn make_targeting_sequence(outputs: &[Coord]) -> Vec<Coord> {
+    let mut coords = Vec::new();
+    for &output in outputs {
+        coords.push(Coord::new(0.0, 0.0));
+        coords.push(Coord::new(0.0, 0.0));
+        coords.push(Coord::new(0.3, 0.3)); // travel
+        coords.push(output);
+        coords.push(output);
+    }
+    coords.push(Coord::new(0.0, 0.0));
+    coords.push(Coord::new(0.0, 0.0));
+    coords
+}
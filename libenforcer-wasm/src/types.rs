@@ -1,4 +1,6 @@
+use crate::game_timer::TimerType;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use wasm_bindgen::prelude::*;
 
 /// A 2D coordinate representing joystick position
@@ -111,6 +113,349 @@ pub struct FuzzAnalysis {
     pub violations: Vec<Violation>,
 }
 
+/// SOCD (simultaneous opposite cardinal direction) resolution behavior
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SocdMode {
+    /// Newest direction wins; releasing it returns to the still-held direction
+    LastInputWins,
+    /// Newest direction wins; releasing it returns to neutral (no reactivation)
+    SecondInputPriority,
+    /// Opposite directions cancel out to neutral
+    Neutral,
+    /// Not enough opposite-direction transitions to tell
+    Unknown,
+}
+
+/// SOCD behavior observed on a single stick axis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SocdAxisAnalysis {
+    /// Inferred resolution mode
+    pub mode: SocdMode,
+    /// Frame-to-frame jumps from one direction straight to the opposite one
+    pub direct_flips: usize,
+    /// Opposite-direction changes that passed through a brief neutral gap
+    pub neutral_crossings: usize,
+    /// Short opposite taps that flipped straight back to the held direction
+    pub reactivations: usize,
+    /// Short opposite taps that released to neutral instead
+    pub releases_to_neutral: usize,
+}
+
+/// SOCD resolution analysis for a box controller
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SocdAnalysis {
+    /// Overall pass/fail verdict against the ruleset
+    pub pass: bool,
+    /// Left/right axis behavior
+    pub horizontal: SocdAxisAnalysis,
+    /// Up/down axis behavior
+    pub vertical: SocdAxisAnalysis,
+    /// Detailed violation descriptions
+    pub violations: Vec<Violation>,
+}
+
+/// A modifier "notch": one output coordinate a box controller lands on,
+/// with fuzz offsets folded in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoordNotch {
+    /// Notch center (most-held coordinate in its fuzz neighborhood)
+    pub coord: Coord,
+    /// Angle of the center in degrees, counter-clockwise from +X
+    pub angle: f64,
+    /// Distance of the center from the origin
+    pub magnitude: f64,
+    /// Number of holds that landed on this notch
+    pub hold_count: usize,
+    /// Game frame each hold started on
+    pub frames: Vec<i32>,
+}
+
+/// C-stick analysis for a box controller
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CStickAnalysis {
+    /// false if any violation was found
+    pub pass: bool,
+    /// All C-stick notches, most-held first
+    pub notches: Vec<CoordNotch>,
+    /// Travel coordinate hit rate (informational unless the ruleset sets a minimum)
+    pub travel_rate: f64,
+    /// true if the C-stick lands on enough angles to be an analog modifier
+    pub analog_modifier: bool,
+    pub violations: Vec<Violation>,
+}
+
+/// Inventory of a player's held coordinates, validated against the ruleset whitelist
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoordInventory {
+    /// false if any notch falls outside the ruleset's allowed coordinates
+    pub pass: bool,
+    /// All notches, most-held first
+    pub notches: Vec<CoordNotch>,
+    /// One violation per disallowed notch
+    pub violations: Vec<Violation>,
+}
+
+/// Outputs of one box modifier layout family, fuzzed or not. Any firmware
+/// shipping the layout produces it, so it doesn't identify a firmware version.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FirmwareProfile {
+    pub name: String,
+    /// Modifier notches in the first quadrant (cardinals are implied)
+    pub coordinates: Vec<Coord>,
+    /// Whether the firmware applies ruleset input fuzzing
+    pub fuzzed: bool,
+}
+
+/// How well a player's outputs match one layout family profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FirmwareMatch {
+    pub name: String,
+    /// Weighted match score from 0.0 to 1.0
+    pub score: f64,
+    /// Share of the player's holds that land on the profile's notches
+    pub explained: f64,
+    /// Share of the profile's notches the player used
+    pub coverage: f64,
+    /// Whether fuzzing behavior agrees with the profile (None if not enough data)
+    pub fuzz_consistent: Option<bool>,
+    /// Player notches the profile can't produce
+    pub unexplained_coords: Vec<Coord>,
+}
+
+/// Layout family fingerprint for a box controller player
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FirmwareFingerprint {
+    /// Every catalog profile, best match first
+    pub matches: Vec<FirmwareMatch>,
+    /// Profiles scoring the same as the best match (including it), which this
+    /// replay can't tell apart. A single entry means the best match is unambiguous.
+    pub tied: Vec<String>,
+}
+
+/// Physical gate shape of an analog stick
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GateShape {
+    Octagon,
+    Circle,
+    /// Too little of the rim was visited to tell
+    Unknown,
+}
+
+/// An angle where an analog stick rests on its rim
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalogNotch {
+    /// Angle in degrees (0-360, counter-clockwise from +X) of the modal coordinate
+    pub angle: f64,
+    /// Most common raw coordinate held at this notch
+    pub coord: Coord,
+    /// Number of rim holds at this notch
+    pub hold_count: usize,
+    /// Number of distinct raw coordinates among those holds
+    pub distinct_coords: usize,
+    /// Share of holds on the modal coordinate
+    pub concentration: f64,
+    /// true if the holds snap to one exact value (firmware notch remapping)
+    pub remapped: bool,
+}
+
+/// Analog stick gate and notch calibration analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalogCalibration {
+    /// false if remapped notches exceed the ruleset's limit
+    pub pass: bool,
+    pub gate_shape: GateShape,
+    /// Maximum raw magnitude reached in each 5° bin, starting at 0°
+    pub gate_radii: Vec<f64>,
+    pub notches: Vec<AnalogNotch>,
+    pub violations: Vec<Violation>,
+}
+
+/// Which kind of angle a snapping axis lies on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SnapKind {
+    Cardinal,
+    Diagonal,
+    /// Any other angle where coordinates pile up
+    Arbitrary,
+}
+
+/// How strongly stick coordinates collapse onto one axis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapAxis {
+    /// Angle in degrees (0-360, counter-clockwise from +X)
+    pub angle: f64,
+    pub kind: SnapKind,
+    /// Coordinates within half a raw unit of the axis
+    pub on_axis: usize,
+    /// Coordinates in the band beside the axis (but not on it)
+    pub near_axis: usize,
+    /// on_axis / (on_axis + near_axis)
+    pub score: f64,
+    /// First frames with a coordinate on the axis
+    pub frames: Vec<usize>,
+}
+
+/// Analog snapping analysis (e.g. GoomWave cardinal snapping)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnappingAnalysis {
+    /// false if any axis snaps
+    pub pass: bool,
+    /// Highest score over all judged axes (0.0-1.0)
+    pub score: f64,
+    /// Axes judged to snap
+    pub axes: Vec<SnapAxis>,
+    pub violations: Vec<Violation>,
+}
+
+/// How strongly an analog stick's release rebound is filtered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SnapbackFilter {
+    /// Rebound after release looks like an unmodified stick
+    Unfiltered,
+    /// Rebound noticeably reduced
+    Light,
+    /// Rebound almost entirely removed
+    Aggressive,
+    /// Too few releases to tell
+    Unknown,
+}
+
+/// Main stick release (snapback) analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapbackAnalysis {
+    /// false if the inferred filter is not allowed by the ruleset
+    pub pass: bool,
+    pub filter: SnapbackFilter,
+    /// 0.0 (natural rebound) to 1.0 (no rebound at all)
+    pub strength: f64,
+    /// Smash inputs released back through neutral
+    pub releases: usize,
+    /// Releases with a small overshoot past neutral
+    pub rebounds: usize,
+    /// rebounds / releases
+    pub rebound_rate: f64,
+    /// Mean overshoot of those rebounds (raw units)
+    pub mean_rebound: f64,
+    /// Releases whose overshoot left the deadzone on the opposite side
+    pub deadzone_crossings: usize,
+    pub violations: Vec<Violation>,
+}
+
+/// Which distribution a remapping gap was found in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RemapAxis {
+    X,
+    Y,
+    /// Distance from neutral
+    Magnitude,
+}
+
+/// A range of raw values (inclusive) the stick never outputs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemapGap {
+    pub axis: RemapAxis,
+    pub start: i32,
+    pub end: i32,
+}
+
+/// A rectangle of raw stick values (inclusive), compared by absolute value
+/// so one entry covers all four quadrants
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StickRegion {
+    pub x_min: i32,
+    pub x_max: i32,
+    pub y_min: i32,
+    pub y_max: i32,
+}
+
+impl StickRegion {
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        (self.x_min..=self.x_max).contains(&x.abs()) && (self.y_min..=self.y_max).contains(&y.abs())
+    }
+}
+
+/// Analog curve remapping (ESS-style) analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemapAnalysis {
+    /// false if the curve looks remapped
+    pub pass: bool,
+    /// X and Y values with enough samples around them to be judged
+    pub dense_values: usize,
+    /// X and Y values among those never output
+    pub gap_values: usize,
+    /// gap_values / dense_values
+    pub gap_fraction: f64,
+    /// Forbidden output ranges on each axis and in magnitude
+    pub gaps: Vec<RemapGap>,
+    /// Frames whose raw output fell in a ruleset forbidden region
+    pub forbidden_region_frames: usize,
+    pub violations: Vec<Violation>,
+}
+
+/// SDI inputs during one hitlag window (a hit or shield hit the player received)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SdiHit {
+    /// First and last frame of hitlag (game frame numbers, inclusive)
+    pub start_frame: i32,
+    pub end_frame: i32,
+    /// Frames the stick entered a new SDI region (cardinal or diagonal)
+    pub sdi_inputs: usize,
+    /// Distance (game units) the character moved during hitlag
+    pub displacement: f64,
+    /// Most displacement legal SDI could produce in this window
+    pub max_legal_displacement: f64,
+    /// true if displacement exceeds the legal maximum
+    pub outlier: bool,
+}
+
+/// Overlapping SDI rule violations merged into one mashing incident
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SdiIncident {
+    /// First and last frame covered by the violations (game frame numbers, inclusive)
+    pub start_frame: i32,
+    pub end_frame: i32,
+    /// SDI rules (1-3) triggered during the incident
+    pub rules: Vec<u8>,
+    /// Number of raw rule violations merged into the incident
+    pub count: usize,
+}
+
+/// A minimum time between two sets of action states.
+/// Entering a `to_states` state sooner than `min_frames` after the last
+/// frame spent in a `from_states` state is a violation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitionRule {
+    /// Display name, used at the start of each violation reason
+    pub name: String,
+    pub from_states: Vec<u16>,
+    pub to_states: Vec<u16>,
+    pub min_frames: i32,
+    /// Controller types the rule applies to
+    pub controller_types: Vec<ControllerType>,
+    /// Skip transitions entered with the C-stick (C-stick attacks bypass the main stick)
+    #[serde(default)]
+    pub main_stick_only: bool,
+    /// Only count transitions entered with the main stick at least this far up,
+    /// e.g. tap jumps rather than jumps from a button
+    #[serde(default)]
+    pub min_stick_y: Option<f64>,
+}
+
+/// Result of one transition rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitionRuleResult {
+    pub name: String,
+    pub pass: bool,
+    pub violations: Vec<Violation>,
+}
+
+/// Minimum transition time analysis, one result per applicable rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitionAnalysis {
+    pub pass: bool,
+    pub rules: Vec<TransitionRuleResult>,
+}
+
 /// Controller type classification
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ControllerType {
@@ -123,19 +468,355 @@ pub enum ControllerType {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlayerAnalysis {
     pub controller_type: ControllerType,
+    /// Team color (0 red, 1 blue, 2 green) in team games
+    pub team: Option<u8>,
     /// Aggregate verdict: true if all applicable checks pass
     pub is_legal: bool,
 
     // Box controller checks (None if analog)
     pub travel_time: Option<CheckResult>,
     pub disallowed_cstick: Option<CheckResult>,
+    pub cstick: Option<CStickAnalysis>,
     pub crouch_uptilt: Option<CheckResult>,
+    /// One violation per incident, or per rule match with `Ruleset::sdi_raw_violations`
     pub sdi: Option<CheckResult>,
+    pub sdi_incidents: Option<Vec<SdiIncident>>,
+    /// Per-hit SDI input counts and displacement (None if the replay has no hitlag data)
+    pub sdi_hits: Option<Vec<SdiHit>>,
+    /// Hits where SDI moved the player further than legally possible
+    pub sdi_distance: Option<CheckResult>,
     pub input_fuzzing: Option<FuzzAnalysis>,
+    /// Fuzzing of the other analog channels, reported separately from the main stick
+    pub cstick_fuzzing: Option<FuzzAnalysis>,
+    pub l_trigger_fuzzing: Option<FuzzAnalysis>,
+    pub r_trigger_fuzzing: Option<FuzzAnalysis>,
+    pub socd: Option<SocdAnalysis>,
+    pub coord_inventory: Option<CoordInventory>,
+    pub firmware: Option<FirmwareFingerprint>,
 
     // Analog controller checks (None if box)
     pub goomwave: Option<CheckResult>,
     pub uptilt_rounding: Option<CheckResult>,
+    pub analog_calibration: Option<AnalogCalibration>,
+    pub snapping: Option<SnappingAnalysis>,
+    pub snapback: Option<SnapbackAnalysis>,
+    pub remapping: Option<RemapAnalysis>,
+
+    // Checks for both controller types
+    pub transitions: TransitionAnalysis,
+
+    /// Replay version, missing data and how faithfully each check could run
+    pub data_quality: DataQuality,
+}
+
+/// How faithfully a check could run on a replay's data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CheckFidelity {
+    /// All the data the check reads was recorded
+    Full,
+    /// The check ran on approximated or incomplete data. Its result is
+    /// reported but only fails the player with `Ruleset::fail_on_degraded_data`.
+    Degraded,
+    /// The check needs data the replay doesn't have and didn't run
+    Skipped,
+}
+
+impl CheckFidelity {
+    /// Whether a failure at this fidelity should count against the player
+    pub fn can_fail(self, fail_on_degraded_data: bool) -> bool {
+        match self {
+            CheckFidelity::Full => true,
+            CheckFidelity::Degraded => fail_on_degraded_data,
+            CheckFidelity::Skipped => false,
+        }
+    }
+}
+
+/// A check that can fail a player, serialized as its `PlayerAnalysis` field name
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckId {
+    TravelTime,
+    DisallowedCstick,
+    Cstick,
+    CrouchUptilt,
+    Sdi,
+    SdiDistance,
+    InputFuzzing,
+    CstickFuzzing,
+    LTriggerFuzzing,
+    RTriggerFuzzing,
+    Socd,
+    CoordInventory,
+    Goomwave,
+    UptiltRounding,
+    AnalogCalibration,
+    Snapping,
+    Snapback,
+    Remapping,
+    Transitions,
+}
+
+/// Fidelity of one check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckQuality {
+    pub check: CheckId,
+    pub fidelity: CheckFidelity,
+    /// Why the check was degraded or skipped
+    pub reasons: Vec<String>,
+}
+
+/// What data a player's analysis had to work with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataQuality {
+    /// Slippi replay format version, e.g. "3.16.0"
+    pub slippi_version: String,
+    /// Replay fields not recorded by this Slippi version
+    pub missing_fields: Vec<String>,
+    /// Rolled-back frame copies (netplay) discarded so only finalized frames are checked
+    pub rollback_frames: usize,
+    /// Main stick streams reconstructed from another stream
+    pub approximated_streams: Vec<StickStream>,
+    /// One entry per check that was run or skipped for this controller type
+    pub checks: Vec<CheckQuality>,
+}
+
+impl DataQuality {
+    /// Whether a check's failure should count against the player.
+    /// Checks without a quality entry count as run on full data.
+    pub fn can_fail(&self, check: CheckId, fail_on_degraded_data: bool) -> bool {
+        self.checks
+            .iter()
+            .find(|q| q.check == check)
+            .is_none_or(|q| q.fidelity.can_fail(fail_on_degraded_data))
+    }
+}
+
+/// Which handwarmer rule a game triggered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HandwarmerRule {
+    /// The game lasted under a minute
+    ShortGame,
+    /// A player held the stick in the deadzone for over 10 seconds while in play
+    IdleInDeadzone,
+}
+
+/// Stock count of one port
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortStocks {
+    pub port: u8,
+    pub stocks: u8,
+}
+
+/// Other hints that a game wasn't a real set game. Informational only;
+/// they don't affect `HandwarmerAnalysis::is_handwarmer`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandwarmerSignals {
+    /// Finalized frames in the game
+    pub frames: usize,
+    /// Nobody took damage or lost a stock
+    pub no_damage_dealt: bool,
+    /// Port that quit out with L+R+A+Start, if the game ended that way
+    pub lras_initiator: Option<u8>,
+    pub stage_id: u16,
+    pub timer_seconds: u32,
+    /// Tournament stage, 8 minute timer and 4 stocks each
+    pub tournament_settings: bool,
+    /// Stocks left for each port on the last frame
+    pub end_stocks: Vec<PortStocks>,
+}
+
+/// Whether a game is a handwarmer, which rule decided it and the evidence
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandwarmerAnalysis {
+    pub is_handwarmer: bool,
+    pub rule: Option<HandwarmerRule>,
+    /// Port responsible (None for game-wide rules)
+    pub port: Option<u8>,
+    /// Game frames the rule triggered over, inclusive
+    pub start_frame: Option<i32>,
+    pub end_frame: Option<i32>,
+    pub signals: HandwarmerSignals,
+}
+
+/// One player's verdict within a team summary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamMember {
+    pub port: u8,
+    pub controller_type: ControllerType,
+    pub is_legal: bool,
+}
+
+/// Verdicts of every player on one team
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamSummary {
+    /// Team color (0 red, 1 blue, 2 green)
+    pub team: u8,
+    pub members: Vec<TeamMember>,
+    /// true if every member passed
+    pub is_legal: bool,
+    /// Teammates use different controller types, e.g. a box and a GCC on the
+    /// same console. Each is checked against its own controller's rules.
+    pub mixed_controller_types: bool,
+}
+
+/// Every player's analysis and the game-level verdicts, from one pass over the frames
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameAnalysis {
+    /// Analysis of each present player, by port
+    pub players: BTreeMap<u8, PlayerAnalysis>,
+    /// true if every player passed
+    pub is_legal: bool,
+    pub handwarmer: HandwarmerAnalysis,
+    pub outcome: GameOutcome,
+    /// Empty for games without teams
+    pub teams: Vec<TeamSummary>,
+}
+
+/// Where a game was played, from the Slippi match id and scene
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameMode {
+    /// Local game, not over Slippi Online
+    Offline,
+    OnlineRanked,
+    OnlineUnranked,
+    OnlineDirect,
+    OnlineTeams,
+    /// Slippi Online with no recognisable match id
+    Online,
+}
+
+/// Per-player settings and identity from the game start block
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerMetadata {
+    pub port: u8,
+    pub character_id: u8,
+    /// 0 human, 1 CPU, 2 demo
+    pub player_type: u8,
+    pub character_color: u8,
+    /// Team color (0 red, 1 blue, 2 green) in team games
+    pub team: Option<u8>,
+    pub stocks: u8,
+    /// In-game name tag (Slippi v1.3+)
+    pub name_tag: Option<String>,
+    /// Slippi Online display name, connect code and user id (Slippi v3.9+)
+    pub netplay_name: Option<String>,
+    pub connect_code: Option<String>,
+    pub slippi_uid: Option<String>,
+}
+
+/// Everything a replay records about its game besides the frames
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameMetadata {
+    /// ISO 8601 start time, as written by the recording client
+    pub start_at: Option<String>,
+    /// "dolphin", "nintendont" or "network"
+    pub played_on: Option<String>,
+    /// Nintendont console nickname
+    pub console_nick: Option<String>,
+    /// Slippi replay format version, e.g. "3.16.0"
+    pub slippi_version: String,
+    /// Game version: PAL instead of NTSC (Slippi v1.5+)
+    pub is_pal: Option<bool>,
+    /// Frozen Pokémon Stadium (Slippi v2.0+)
+    pub is_frozen_ps: Option<bool>,
+    pub game_mode: GameMode,
+    pub is_teams: bool,
+    pub stage_id: u16,
+    pub timer_type: TimerType,
+    pub timer_seconds: u32,
+    /// Slippi Online match this game belongs to (Slippi v3.14+)
+    pub match_id: Option<String>,
+    pub game_number: Option<u32>,
+    pub tiebreaker_number: Option<u32>,
+    pub players: Vec<PlayerMetadata>,
+}
+
+/// How a game ended, from the replay's end block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameEndMethod {
+    Unresolved,
+    /// The timer ran out
+    Time,
+    /// Stocks ran out
+    Game,
+    Resolved,
+    /// Quit out with L+R+A+Start
+    NoContest,
+}
+
+/// One port's result at the end of a game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerOutcome {
+    pub port: u8,
+    /// 0 is the winner (None before Slippi v3.13)
+    pub placement: Option<u8>,
+    /// Stocks and percent on the last frame
+    pub stocks: u8,
+    pub percent: f32,
+}
+
+/// How and by whom a game was decided
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameOutcome {
+    /// None if the replay has no end block (crash or disconnect)
+    pub end_method: Option<GameEndMethod>,
+    /// Port that quit out with L+R+A+Start (None before Slippi v2.0)
+    pub lras_initiator: Option<u8>,
+    pub players: Vec<PlayerOutcome>,
+}
+
+/// The three forms main stick data is kept in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StickStream {
+    /// Raw int8 hardware values (-128..127) as recorded by Slippi
+    Raw,
+    /// The game engine's processed `pre.joystick` (-1.0..1.0)
+    Engine,
+    /// Raw values run through this crate's `process_analog_stick`
+    Processed,
+}
+
+/// What a player was doing on one frame, so per-frame vectors stay aligned
+/// with game frames even when the player isn't in play
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FrameStatus {
+    Active,
+    /// No data for this port on this frame; inputs are neutral placeholders
+    Absent,
+    /// In a death animation, or out of stocks
+    Dead,
+    /// On the respawn platform, or still invincible after leaving it
+    RespawnInvincible,
+}
+
+/// Which frames a check reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FrameUse {
+    /// Every frame, absent ones as placeholders, so index i is always game
+    /// frame i - 123. For checks that rely on frame adjacency or action states.
+    Aligned,
+    /// Only frames the player was present for (dead and respawning included,
+    /// since inputs are still recorded then). For checks over input statistics.
+    Present,
+}
+
+/// One main stick stream for every frame
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StickStreamData {
+    pub coords: Vec<Coord>,
+    /// true if the X axis had to be reconstructed from another stream on any frame
+    pub approximated_x: bool,
+    /// true if the Y axis had to be reconstructed from another stream on any frame
+    pub approximated_y: bool,
+}
+
+/// All three main stick streams of a player
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StickStreams {
+    pub raw: StickStreamData,
+    pub engine: StickStreamData,
+    pub processed: StickStreamData,
 }
 
 /// Joystick region classification (9 regions based on 0.2875 threshold)
@@ -151,3 +832,32 @@ pub enum JoystickRegion {
     S = 7,
     W = 8,
 }
+
+/// Thresholds for classifying a stick coordinate into a region.
+/// A coordinate is in the deadzone while both axes are inside `deadzone`,
+/// a diagonal when both axes are past it and the magnitude reaches
+/// `min_magnitude`, and a cardinal when one axis reaches both. Anything
+/// else (only possible with a `min_magnitude` above `deadzone`) is tilt.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RegionGeometry {
+    pub deadzone: f64,
+    /// Whether an axis exactly on `deadzone` is still inside the deadzone
+    pub inclusive_deadzone: bool,
+    pub min_magnitude: f64,
+}
+
+impl RegionGeometry {
+    /// The 9 joystick regions: no tilt zone, deadzone bound exclusive
+    pub const JOYSTICK: RegionGeometry = RegionGeometry {
+        deadzone: 0.2875,
+        inclusive_deadzone: false,
+        min_magnitude: 0.0,
+    };
+
+    /// SDI regions: a direction only counts for SDI at magnitude 0.7
+    pub const SDI: RegionGeometry = RegionGeometry {
+        deadzone: 0.2875,
+        inclusive_deadzone: true,
+        min_magnitude: 0.7,
+    };
+}
@@ -1,4 +1,4 @@
-use crate::types::{Coord, JoystickRegion};
+use crate::types::{Coord, JoystickRegion, RegionGeometry};
 use std::collections::HashSet;
 
 /// Float equality comparison with epsilon tolerance
@@ -96,24 +96,46 @@ pub fn get_target_coords(coordinates: &[Coord]) -> Vec<Coord> {
 /// Classify joystick position into one of 9 regions
 /// Mirrors TypeScript getJoystickRegion() from index.ts
 pub fn get_joystick_region(x: f64, y: f64) -> JoystickRegion {
-    if x >= 0.2875 && y >= 0.2875 {
-        JoystickRegion::NE
-    } else if x >= 0.2875 && y <= -0.2875 {
-        JoystickRegion::SE
-    } else if x <= -0.2875 && y <= -0.2875 {
-        JoystickRegion::SW
-    } else if x <= -0.2875 && y >= 0.2875 {
-        JoystickRegion::NW
-    } else if y >= 0.2875 {
-        JoystickRegion::N
-    } else if x >= 0.2875 {
-        JoystickRegion::E
-    } else if y <= -0.2875 {
-        JoystickRegion::S
-    } else if x <= -0.2875 {
-        JoystickRegion::W
+    classify_region(x, y, &RegionGeometry::JOYSTICK).unwrap_or(JoystickRegion::DZ)
+}
+
+/// Classify a stick position with the given thresholds.
+/// Returns None in the tilt zone (outside the deadzone, short of `min_magnitude`).
+pub fn classify_region(x: f64, y: f64, geometry: &RegionGeometry) -> Option<JoystickRegion> {
+    let dz = geometry.deadzone;
+    let inside = |v: f64| if geometry.inclusive_deadzone { v.abs() <= dz } else { v.abs() < dz };
+    if inside(x) && inside(y) {
+        return Some(JoystickRegion::DZ);
+    }
+
+    // Diagonals need both axes past the deadzone and enough magnitude
+    if (x.powi(2) + y.powi(2)).sqrt() >= geometry.min_magnitude {
+        if x >= dz && y >= dz {
+            return Some(JoystickRegion::NE);
+        }
+        if x >= dz && y <= -dz {
+            return Some(JoystickRegion::SE);
+        }
+        if x <= -dz && y <= -dz {
+            return Some(JoystickRegion::SW);
+        }
+        if x <= -dz && y >= dz {
+            return Some(JoystickRegion::NW);
+        }
+    }
+
+    // Cardinals need one axis past both thresholds
+    let cardinal = dz.max(geometry.min_magnitude);
+    if y >= cardinal {
+        Some(JoystickRegion::N)
+    } else if x >= cardinal {
+        Some(JoystickRegion::E)
+    } else if y <= -cardinal {
+        Some(JoystickRegion::S)
+    } else if x <= -cardinal {
+        Some(JoystickRegion::W)
     } else {
-        JoystickRegion::DZ
+        None
     }
 }
 
@@ -144,7 +166,7 @@ mod tests {
             Coord { x: 1.0, y: 0.0 },
             Coord { x: 0.0, y: 1.0 },
             Coord { x: -1.0, y: 0.0 },
-            Coord { x: 0.7071, y: 0.7071 }, // 45 degrees
+            Coord { x: std::f64::consts::FRAC_1_SQRT_2, y: std::f64::consts::FRAC_1_SQRT_2 }, // 45 degrees
         ];
 
         let count = count_rim_coords(&rim_coords);
@@ -164,4 +186,49 @@ mod tests {
         let targets = get_target_coords(&coords);
         assert_eq!(targets.len(), 2);
     }
+
+    /// Every coordinate a stick can output, on the 1/80 grid, plus the
+    /// boundary values themselves
+    fn sample_points() -> Vec<(f64, f64)> {
+        let mut axis: Vec<f64> = (-80..=80).map(|v| v as f64 / 80.0).collect();
+        axis.extend([0.2875, -0.2875, 0.7, -0.7, 0.28749, 0.28751]);
+        axis.iter()
+            .flat_map(|&x| axis.iter().map(move |&y| (x, y)))
+            .collect()
+    }
+
+    #[test]
+    fn test_sdi_regions_agree_with_joystick_regions() {
+        for (x, y) in sample_points() {
+            let joystick = get_joystick_region(x, y);
+            match classify_region(x, y, &RegionGeometry::SDI) {
+                // Every SDI direction is the same joystick direction
+                Some(JoystickRegion::DZ) => {}
+                Some(region) => assert_eq!(region, joystick, "({}, {})", x, y),
+                // Tilt is outside the joystick deadzone
+                None => assert_ne!(joystick, JoystickRegion::DZ, "({}, {})", x, y),
+            }
+        }
+    }
+
+    #[test]
+    fn test_deadzones_differ_only_on_the_boundary() {
+        for (x, y) in sample_points() {
+            let sdi_dz = classify_region(x, y, &RegionGeometry::SDI) == Some(JoystickRegion::DZ);
+            let joystick_dz = get_joystick_region(x, y) == JoystickRegion::DZ;
+            if joystick_dz {
+                assert!(sdi_dz, "({}, {})", x, y);
+            } else if sdi_dz {
+                // Inclusive SDI deadzone: an axis sits exactly on the bound
+                assert!(x.abs() == 0.2875 || y.abs() == 0.2875, "({}, {})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_joystick_geometry_has_no_tilt() {
+        for (x, y) in sample_points() {
+            assert!(classify_region(x, y, &RegionGeometry::JOYSTICK).is_some());
+        }
+    }
 }
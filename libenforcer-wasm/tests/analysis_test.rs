@@ -0,0 +1,69 @@
+//! Integration tests for whole-game analysis
+
+#[path = "common/mod.rs"]
+mod common;
+
+#[cfg(not(target_arch = "wasm32"))]
+use libenforcer_wasm::{analysis, checks, handwarmer, parser, ruleset::Ruleset};
+use peppi::io::slippi::de::read as read_slippi;
+use std::io::Cursor;
+
+use common::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_single_pass_matches_per_player_extraction() {
+    let data = read_slp_file("legal/digital/carvac_23.1/Game_20260203T231033.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+
+    let players = parser::extract_all_players(&game);
+    let ports: Vec<u8> = players.iter().map(|(port, _)| *port).collect();
+    assert_eq!(ports, vec![0, 1]);
+
+    for (port, all) in &players {
+        let single = parser::extract_player_data(&game, *port as usize).unwrap();
+        let coords = |data: &parser::PlayerGameData| -> Vec<(f64, f64)> {
+            data.main_coords.iter().map(|c| (c.x, c.y)).collect()
+        };
+        assert_eq!(coords(all), coords(&single));
+        assert_eq!(all.action_states, single.action_states);
+        assert_eq!(all.frame_status, single.frame_status);
+        assert_eq!(all.rollback_frames, single.rollback_frames);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_analyze_game() {
+    let data = read_slp_file("legal/digital/carvac_23.1/Game_20260203T231033.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+
+    let analysis = analysis::analyze_game(&game, &Ruleset::default());
+    assert_eq!(analysis.players.keys().copied().collect::<Vec<u8>>(), vec![0, 1]);
+    for (port, player) in &analysis.players {
+        let data = parser::extract_player_data(&game, *port as usize).unwrap();
+        let single = checks::analyze_player(&data);
+        assert_eq!(player.is_legal, single.is_legal);
+        assert_eq!(player.controller_type, single.controller_type);
+    }
+    assert_eq!(
+        analysis.is_legal,
+        analysis.players.values().all(|p| p.is_legal)
+    );
+    assert_eq!(analysis.handwarmer.is_handwarmer, handwarmer::is_handwarmer(&game));
+    assert_eq!(analysis.outcome.players.len(), 2);
+    assert!(analysis.teams.is_empty());
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_analyze_game_doubles() {
+    let data = read_slp_file("handwarmers/Game_8C56C529AEAA_20231021T125730.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+
+    let analysis = analysis::analyze_game(&game, &Ruleset::default());
+    assert_eq!(analysis.players.len(), 4);
+    assert!(analysis.handwarmer.is_handwarmer);
+    let teams: Vec<u8> = analysis.teams.iter().map(|t| t.team).collect();
+    assert_eq!(teams, vec![1, 2]);
+}
@@ -1,4 +1,5 @@
 // Common test utilities shared across integration tests
+#![allow(dead_code)]
 
 use std::fs;
 use std::path::PathBuf;
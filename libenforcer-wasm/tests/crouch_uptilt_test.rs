@@ -20,8 +20,8 @@ fn test_crouch_uptilt_nonlegal() {
     let player_data = parser::extract_player_data(&game, 3).unwrap();
 
     let result = crouch_uptilt::check(&player_data.main_coords, &player_data.action_states);
-    assert_eq!(
-        result.result, true,
+    assert!(
+        result.result,
         "crouch_uptilt_unnerfed.slp should fail crouch uptilt check"
     );
     assert_eq!(
@@ -52,8 +52,8 @@ fn test_crouch_uptilt_legal() {
     let player_data = parser::extract_player_data(&game, 0).unwrap();
 
     let result = crouch_uptilt::check(&player_data.main_coords, &player_data.action_states);
-    assert_eq!(
-        result.result, false,
+    assert!(
+        !result.result,
         "crouch_uptilt_r18_v2.slp should pass crouch uptilt check"
     );
 }
@@ -66,8 +66,8 @@ fn test_crouch_uptilt_doubles_with_blank_player() {
     let player_data = parser::extract_player_data(&game, 0).unwrap();
 
     let result = crouch_uptilt::check(&player_data.main_coords, &player_data.action_states);
-    assert_eq!(
-        result.result, false,
+    assert!(
+        !result.result,
         "doubles_with_blank_player.slp should pass crouch uptilt check"
     );
 }
@@ -0,0 +1,54 @@
+//! Integration tests for C-stick analysis
+
+#[path = "common/mod.rs"]
+mod common;
+
+#[cfg(not(target_arch = "wasm32"))]
+use libenforcer_wasm::{checks::cstick, parser, ruleset::Ruleset};
+use peppi::io::slippi::de::read as read_slippi;
+use std::io::Cursor;
+
+use common::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_cstick_analog_modifier() {
+    let data = read_slp_file("banned_c_stick_analog_player_1.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+    let player_data = parser::extract_player_data(&game, 0).unwrap();
+    let c_coords = player_data.frames(&player_data.c_coords, cstick::FRAMES);
+    let frame_numbers = player_data.frame_numbers(cstick::FRAMES);
+
+    let analysis = cstick::analyze(&c_coords, &frame_numbers, &Ruleset::default());
+    assert!(!analysis.pass);
+    assert!(analysis.analog_modifier);
+    assert!(analysis.violations[0].reason.contains("forbidden y value 0.6625"));
+    assert!(analysis.violations.iter().any(|v| v.reason.contains("analog C-Stick modifier")));
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_cstick_legal_box() {
+    let data = read_slp_file("legal/digital/carvac_23.1/Game_20260203T231033.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+    let player_data = parser::extract_player_data(&game, 0).unwrap();
+    let c_coords = player_data.frames(&player_data.c_coords, cstick::FRAMES);
+    let frame_numbers = player_data.frame_numbers(cstick::FRAMES);
+
+    let analysis = cstick::analyze(&c_coords, &frame_numbers, &Ruleset::default());
+    assert!(analysis.pass);
+    assert!(!analysis.analog_modifier);
+    assert!(analysis.travel_rate < 0.25);
+
+    // Angled C-stick outputs sit at 58.3°
+    let ruleset = Ruleset {
+        allowed_cstick_angles: Some(vec![58.3]),
+        ..Ruleset::default()
+    };
+    assert!(cstick::analyze(&c_coords, &frame_numbers, &ruleset).pass);
+    let ruleset = Ruleset {
+        allowed_cstick_angles: Some(vec![45.0]),
+        ..Ruleset::default()
+    };
+    assert!(!cstick::analyze(&c_coords, &frame_numbers, &ruleset).pass);
+}
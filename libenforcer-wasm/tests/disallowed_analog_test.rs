@@ -52,8 +52,8 @@ fn test_full_game_with_disallowed_c_stick_value() {
     let player_data = parser::extract_player_data(&game, 0).unwrap();
 
     let result = disallowed_analog::check(&player_data.c_coords);
-    assert_eq!(
-        result.result, true,
+    assert!(
+        result.result,
         "banned_c_stick_analog_player_1.slp should fail C-stick check"
     );
 }
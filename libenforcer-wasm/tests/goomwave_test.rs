@@ -22,8 +22,8 @@ fn test_is_goomwave_negative() {
         let player_data = parser::extract_player_data(&game, 2).unwrap();
 
         let result = goomwave::check(&player_data.main_coords);
-        assert_eq!(
-            result.result, false,
+        assert!(
+            !result.result,
             "Potion {} should not be detected as goomwave",
             i
         );
@@ -38,8 +38,8 @@ fn test_is_goomwave_a_positive() {
     let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
     let player_data = parser::extract_player_data(&game, 0).unwrap();
 
-    assert_eq!(
-        goomwave::has_goomwave_clamping(&player_data.main_coords), true,
+    assert!(
+        goomwave::has_goomwave_clamping(&player_data.main_coords),
         "goomwave_uptilt_p1.slp should have goomwave clamping"
     );
 }
@@ -51,8 +51,8 @@ fn test_is_goomwave_b_positive() {
     let player_data = parser::extract_player_data(&game, 1).unwrap();
 
     let result = goomwave::check(&player_data.main_coords);
-    assert_eq!(
-        result.result, true,
+    assert!(
+        result.result,
         "Game_20250216T194607.slp should be detected as goomwave"
     );
 }
@@ -64,8 +64,8 @@ fn test_is_goomwave_c_positive() {
     let player_data = parser::extract_player_data(&game, 1).unwrap();
 
     let result = goomwave::check(&player_data.main_coords);
-    assert_eq!(
-        result.result, true,
+    assert!(
+        result.result,
         "Game_20250216T194746.slp should be detected as goomwave"
     );
 }
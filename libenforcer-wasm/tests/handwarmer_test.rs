@@ -1,12 +1,12 @@
 //! Integration tests for handwarmer detection
 //! Mirrors TypeScript tests in src/tests/utils.test.ts (Is handwarmer? A/B/C)
-//! Test count: 3
+//! Test count: 5
 
 #[path = "common/mod.rs"]
 mod common;
 
 #[cfg(not(target_arch = "wasm32"))]
-use libenforcer_wasm::handwarmer;
+use libenforcer_wasm::{handwarmer, types::HandwarmerRule};
 use peppi::io::slippi::de::read as read_slippi;
 use std::io::Cursor;
 
@@ -19,9 +19,8 @@ fn test_is_handwarmer_a_techno_not_handwarmers() {
 
     for (filename, data) in &files {
         let game = read_slippi(&mut Cursor::new(data), None).unwrap();
-        assert_eq!(
-            handwarmer::is_handwarmer(&game),
-            false,
+        assert!(
+            !handwarmer::is_handwarmer(&game),
             "{} should NOT be a handwarmer",
             filename
         );
@@ -35,9 +34,8 @@ fn test_is_handwarmer_b_handwarmer_files() {
 
     for (filename, data) in &files {
         let game = read_slippi(&mut Cursor::new(data), None).unwrap();
-        assert_eq!(
+        assert!(
             handwarmer::is_handwarmer(&game),
-            true,
             "{} should be a handwarmer",
             filename
         );
@@ -48,9 +46,41 @@ fn test_is_handwarmer_b_handwarmer_files() {
 fn test_is_handwarmer_c_doubles_not_handwarmer() {
     let data = read_slp_file("doubles_match_1.slp");
     let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
-    assert_eq!(
-        handwarmer::is_handwarmer(&game),
-        false,
+    assert!(
+        !handwarmer::is_handwarmer(&game),
         "doubles_match_1.slp should NOT be a handwarmer"
     );
 }
+
+#[test]
+fn test_handwarmer_analysis_short_game() {
+    // 275 frame game quit out by port 4
+    let data = read_slp_file("handwarmers/Game_8C56C529AEAA_20231021T125730.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+    let analysis = handwarmer::analyze(&game);
+
+    assert!(analysis.is_handwarmer);
+    assert_eq!(analysis.rule, Some(HandwarmerRule::ShortGame));
+    assert_eq!(analysis.port, None);
+    assert_eq!(analysis.start_frame, Some(-123));
+    assert_eq!(analysis.end_frame, Some(151));
+    assert_eq!(analysis.signals.frames, 275);
+    assert_eq!(analysis.signals.lras_initiator, Some(3));
+    assert!(analysis.signals.no_damage_dealt);
+    assert!(analysis.signals.tournament_settings);
+    assert_eq!(analysis.signals.end_stocks.len(), 4);
+}
+
+#[test]
+fn test_handwarmer_analysis_real_game() {
+    let data = read_slp_file("legal/digital/carvac_23.1/Game_20260203T231033.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+    let analysis = handwarmer::analyze(&game);
+
+    assert!(!analysis.is_handwarmer);
+    assert_eq!(analysis.rule, None);
+    assert_eq!(analysis.signals.lras_initiator, None);
+    assert!(!analysis.signals.no_damage_dealt);
+    let stocks: Vec<(u8, u8)> = analysis.signals.end_stocks.iter().map(|s| (s.port, s.stocks)).collect();
+    assert_eq!(stocks, vec![(0, 0), (1, 2)]);
+}
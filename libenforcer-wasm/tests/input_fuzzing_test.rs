@@ -9,7 +9,7 @@
 mod common;
 
 #[cfg(not(target_arch = "wasm32"))]
-use libenforcer_wasm::{checks::input_fuzzing, parser, types::Coord, utils::is_box_controller};
+use libenforcer_wasm::{checks::{self, input_fuzzing}, parser, types::Coord, utils::is_box_controller};
 use peppi::io::slippi::de::read as read_slippi;
 use std::io::Cursor;
 
@@ -28,8 +28,8 @@ fn test_input_fuzzing_legal_player() {
         let player_data = parser::extract_player_data(&game, 0).unwrap();
 
         let result = input_fuzzing::check(&player_data.main_coords);
-        assert_eq!(
-            result.result, false,
+        assert!(
+            !result.result,
             "G{} player 1 (fuzzed) should pass fuzzing check",
             i
         );
@@ -47,8 +47,8 @@ fn test_input_fuzzing_nonlegal_player() {
         let player_data = parser::extract_player_data(&game, 1).unwrap();
 
         let result = input_fuzzing::check(&player_data.main_coords);
-        assert_eq!(
-            result.result, true,
+        assert!(
+            result.result,
             "G{} player 2 (unfuzzed) should fail fuzzing check",
             i
         );
@@ -320,3 +320,18 @@ fn test_analyze_fuzzing_legal_player() {
         analysis.violations.iter().map(|v| &v.reason).collect::<Vec<_>>()
     );
 }
+
+#[test]
+fn test_other_channels_reported_separately() {
+    let data = read_slp_file("legal/digital/carvac_23.1/Game_20260203T231033.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+    let player_data = parser::extract_player_data(&game, 0).unwrap();
+
+    let analysis = checks::analyze_player(&player_data);
+    assert!(analysis.cstick_fuzzing.expect("Box player").pass);
+    // Digital trigger presses only, nothing to fuzz
+    let l_trigger = analysis.l_trigger_fuzzing.expect("Box player");
+    assert!(l_trigger.pass);
+    assert_eq!(l_trigger.total_fuzz_events, 0);
+    assert!(analysis.r_trigger_fuzzing.is_some());
+}
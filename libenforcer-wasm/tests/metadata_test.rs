@@ -0,0 +1,53 @@
+//! Integration tests for game metadata
+
+#[path = "common/mod.rs"]
+mod common;
+
+#[cfg(not(target_arch = "wasm32"))]
+use libenforcer_wasm::{game_timer::TimerType, metadata, types::GameMode};
+use peppi::io::slippi::de::read as read_slippi;
+use std::io::Cursor;
+
+use common::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_metadata_online() {
+    let data = read_slp_file("legal/digital/carvac_23.1/Game_20260203T231033.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+    let metadata = metadata::game_metadata(&game);
+
+    assert_eq!(metadata.start_at.as_deref(), Some("2026-02-04T04:10:33Z"));
+    assert_eq!(metadata.played_on.as_deref(), Some("dolphin"));
+    assert_eq!(metadata.slippi_version, "3.19.0");
+    assert_eq!(metadata.game_mode, GameMode::OnlineUnranked);
+    assert_eq!(metadata.match_id.as_deref(), Some("mode.unranked-2026-02-04T04:07:29.23Z-1"));
+    assert_eq!(metadata.game_number, Some(2));
+    assert_eq!(metadata.tiebreaker_number, Some(0));
+    assert_eq!(metadata.timer_type, TimerType::Decreasing);
+    assert_eq!(metadata.timer_seconds, 480);
+    assert!(!metadata.is_teams);
+    assert_eq!(metadata.is_pal, Some(false));
+
+    let p1 = &metadata.players[0];
+    assert_eq!(p1.netplay_name.as_deref(), Some("Epoxy Mafia"));
+    assert_eq!(p1.connect_code.as_deref(), Some("CARV#889"));
+    assert!(p1.slippi_uid.is_some());
+    assert_eq!(p1.team, None);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_metadata_console() {
+    let data = read_slp_file("handwarmers/Game_8C56C529AEAA_20231020T183249.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+    let metadata = metadata::game_metadata(&game);
+
+    assert_eq!(metadata.played_on.as_deref(), Some("nintendont"));
+    assert_eq!(metadata.console_nick.as_deref(), Some("btssmash"));
+    assert_eq!(metadata.game_mode, GameMode::Offline);
+    assert_eq!(metadata.match_id, None);
+    assert_eq!(metadata.players.len(), 2);
+    assert_eq!(metadata.players[0].netplay_name, None);
+    assert_eq!(metadata.players[1].player_type, 1);
+}
@@ -0,0 +1,45 @@
+//! Integration tests for reading how a game ended
+
+#[path = "common/mod.rs"]
+mod common;
+
+#[cfg(not(target_arch = "wasm32"))]
+use libenforcer_wasm::{outcome, types::GameEndMethod};
+use peppi::io::slippi::de::read as read_slippi;
+use std::io::Cursor;
+
+use common::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_outcome_stocks() {
+    let data = read_slp_file("legal/digital/carvac_23.1/Game_20260203T231033.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+    let outcome = outcome::game_outcome(&game);
+
+    assert_eq!(outcome.end_method, Some(GameEndMethod::Game));
+    assert_eq!(outcome.lras_initiator, None);
+    assert_eq!(outcome.players.len(), 2);
+
+    // Port 2 won with 2 stocks at 11.75%
+    let winner = &outcome.players[1];
+    assert_eq!(winner.port, 1);
+    assert_eq!(winner.placement, Some(0));
+    assert_eq!(winner.stocks, 2);
+    assert!((winner.percent - 11.75).abs() < 0.01);
+    assert_eq!(outcome.players[0].placement, Some(1));
+    assert_eq!(outcome.players[0].stocks, 0);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_outcome_lras() {
+    let data = read_slp_file("handwarmers/Game_8C56C529AEAA_20231020T183249.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+    let outcome = outcome::game_outcome(&game);
+
+    assert_eq!(outcome.end_method, Some(GameEndMethod::NoContest));
+    assert_eq!(outcome.lras_initiator, Some(0));
+    let stocks: Vec<(u8, u8)> = outcome.players.iter().map(|p| (p.port, p.stocks)).collect();
+    assert_eq!(stocks, vec![(0, 2), (1, 4)]);
+}
@@ -0,0 +1,49 @@
+//! Integration tests for analog curve remapping detection
+//!
+//! Test data:
+//!   legal/analog/traveltime/ — Ports 2 and 3 unmodified analog
+
+#[path = "common/mod.rs"]
+mod common;
+
+#[cfg(not(target_arch = "wasm32"))]
+use libenforcer_wasm::{checks::remapping, parser, ruleset::Ruleset, types::Coord};
+use peppi::io::slippi::de::read as read_slippi;
+use std::io::Cursor;
+
+use common::*;
+
+#[test]
+fn test_remapping_legal_analog_native_curve() {
+    let files = read_slp_dir("legal/analog/traveltime");
+    assert!(!files.is_empty(), "Should find traveltime replay files");
+
+    for (filename, data) in &files {
+        let game = read_slippi(&mut Cursor::new(data), None).unwrap();
+        for port in [2, 3] {
+            let player_data = parser::extract_player_data(&game, port).unwrap();
+            let analysis = remapping::analyze(&player_data.raw_joystick_coords, &Ruleset::default());
+            assert!(analysis.pass, "{} port {} should have a native curve", filename, port);
+            assert_eq!(analysis.gap_values, 0);
+        }
+    }
+}
+
+#[test]
+fn test_remapping_curve_applied_to_legal_inputs() {
+    // The same real inputs passed through an ESS-style curve must be flagged
+    let data = read_slp_file("legal/analog/traveltime/Game_8C56C529AEAA_20231022T181554.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+    let player_data = parser::extract_player_data(&game, 2).unwrap();
+
+    let curve = |v: f64| (v.signum() * 80.0 * (v.abs() / 80.0).powf(0.6)).round();
+    let remapped: Vec<Coord> = player_data
+        .raw_joystick_coords
+        .iter()
+        .map(|c| Coord::new(curve(c.x), curve(c.y)))
+        .collect();
+
+    let analysis = remapping::analyze(&remapped, &Ruleset::default());
+    assert!(!analysis.pass);
+    assert!(analysis.gap_values >= 20);
+}
@@ -1,15 +1,16 @@
 //! Integration tests for SDI check
 //! Mirrors TypeScript tests in src/tests/sdi.test.ts
-//! Test count: 12
+//! Test count: 17
 
 #[path = "common/mod.rs"]
 mod common;
 
 #[cfg(not(target_arch = "wasm32"))]
 use libenforcer_wasm::{
-    checks::sdi::{self, get_sdi_region, is_diagonal_adjacent, SDIRegion},
+    checks::sdi::{self, get_sdi_region, is_diagonal_adjacent, SDIRegion, SdiTraceEvent},
     parser,
-    types::Coord,
+    ruleset::Ruleset,
+    types::{Coord, JoystickRegion},
 };
 use peppi::io::slippi::de::read as read_slippi;
 use std::io::Cursor;
@@ -101,12 +102,12 @@ fn test_sdi_from_legal_digital_file() {
 
 #[test]
 fn test_is_diagonal_adjacent() {
-    assert_eq!(is_diagonal_adjacent(SDIRegion::SE, SDIRegion::NW), false);
-    assert_eq!(is_diagonal_adjacent(SDIRegion::SE, SDIRegion::SE), false);
-    assert_eq!(is_diagonal_adjacent(SDIRegion::SE, SDIRegion::DZ), false);
-    assert_eq!(is_diagonal_adjacent(SDIRegion::SE, SDIRegion::SW), true);
-    assert_eq!(is_diagonal_adjacent(SDIRegion::NW, SDIRegion::NE), true);
-    assert_eq!(is_diagonal_adjacent(SDIRegion::SW, SDIRegion::SE), true);
+    assert!(!is_diagonal_adjacent(SDIRegion::SE, SDIRegion::NW));
+    assert!(!is_diagonal_adjacent(SDIRegion::SE, SDIRegion::SE));
+    assert!(!is_diagonal_adjacent(SDIRegion::SE, SDIRegion::DZ));
+    assert!(is_diagonal_adjacent(SDIRegion::SE, SDIRegion::SW));
+    assert!(is_diagonal_adjacent(SDIRegion::NW, SDIRegion::NE));
+    assert!(is_diagonal_adjacent(SDIRegion::SW, SDIRegion::SE));
 }
 
 #[test]
@@ -132,7 +133,7 @@ fn test_sdi_manual_coords() {
         Coord { x: 1.0, y: 0.0 },
     ];
     assert!(
-        sdi::fails_sdi_rule_one(&coords).len() >= 1,
+        !sdi::fails_sdi_rule_one(&coords).is_empty(),
         "Rapid SDI should trigger violation"
     );
 
@@ -198,7 +199,7 @@ fn test_sdi_manual_coords() {
         Coord { x: 0.4, y: 0.0 },
     ];
     assert!(
-        sdi::fails_sdi_rule_one(&coords).len() >= 1,
+        !sdi::fails_sdi_rule_one(&coords).is_empty(),
         "Travel time after SDI should not exempt"
     );
 }
@@ -228,8 +229,8 @@ fn test_sdi_legal_b() {
     let player_data = parser::extract_player_data(&game, 0).unwrap();
 
     let result = sdi::check(&player_data.main_coords);
-    assert_eq!(
-        result.result, false,
+    assert!(
+        !result.result,
         "sdi_t20_cardinal_diagonal.slp should pass all SDI checks"
     );
     assert_eq!(
@@ -253,7 +254,7 @@ fn test_sdi_legal_c() {
     let player_data = parser::extract_player_data(&game, 0).unwrap();
 
     let result = sdi::check(&player_data.main_coords);
-    assert_eq!(result.result, false, "sdi_mash.slp should pass all SDI checks");
+    assert!(!result.result, "sdi_mash.slp should pass all SDI checks");
     assert_eq!(
         sdi::fails_sdi_rule_one(&player_data.main_coords).len(),
         0
@@ -316,8 +317,8 @@ fn test_sdi_nonlegal_a() {
 
     // Fails rule #1
     let result = sdi::check(&player_data.main_coords);
-    assert_eq!(
-        result.result, true,
+    assert!(
+        result.result,
         "sdi_tas_neutral_cardinal.slp should fail SDI check"
     );
 
@@ -372,8 +373,8 @@ fn test_sdi_nonlegal_c() {
 
     // Fails rule #3 but not rule #1
     let result = sdi::check(&player_data.main_coords);
-    assert_eq!(
-        result.result, true,
+    assert!(
+        result.result,
         "sdi_unnerfed.slp should fail SDI check"
     );
     assert_eq!(
@@ -397,3 +398,110 @@ fn test_sdi_nonlegal_c() {
         ]
     );
 }
+
+#[test]
+fn test_sdi_restricted_to_hitlag() {
+    // Stick wiggles outside hitlag are not SDI
+    let data = read_slp_file("legal/digital/carvac_23.1/Game_20260203T232245.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+    let player_data = parser::extract_player_data(&game, 0).unwrap();
+
+    assert!(sdi::check(&player_data.main_coords).result);
+    assert!(!sdi::check_player(&player_data, &Ruleset::default()).result);
+
+    // Unnerfed diagonal alternation during hitlag is still caught
+    let data = read_slp_file("nonlegal/digital/sdi/sdi_unnerfed.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+    let player_data = parser::extract_player_data(&game, 3).unwrap();
+
+    let result = sdi::check_player(&player_data, &Ruleset::default());
+    assert!(result.result);
+    assert_eq!(result.details.len(), 4);
+    assert_eq!(result.details[0].metric as i32, 157 - 123);
+
+    // Both SDI paths report game frames
+    let incidents = sdi::player_incidents(&player_data, &Ruleset::default());
+    let coords = player_data.stick_frames(sdi::STICK_STREAM, sdi::FRAMES);
+    let grouped = sdi::check_incidents(&incidents, &coords);
+    assert_eq!(grouped.details[0].metric, result.details[0].metric);
+}
+
+#[test]
+fn test_sdi_hits() {
+    let data = read_slp_file("nonlegal/digital/sdi/sdi_tas_neutral_cardinal.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+    let player_data = parser::extract_player_data(&game, 3).unwrap();
+
+    let hits = sdi::player_hits(&player_data, &Ruleset::default()).expect("Replay records hitlag");
+    assert_eq!(hits.len(), 6);
+    // First hit: 4 frames of hitlag with two taps to the left
+    assert_eq!(hits[0].start_frame, 21);
+    assert_eq!(hits[0].end_frame, 24);
+    assert_eq!(hits[0].sdi_inputs, 2);
+    assert!((hits[0].displacement - 12.0).abs() < 0.01);
+    assert!(hits.iter().all(|hit| !hit.outlier));
+}
+
+#[test]
+fn test_sdi_distance_legal_mash() {
+    let data = read_slp_file("legal/digital/sdi/sdi_mash.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+
+    for port in 0..4 {
+        let Some(player_data) = parser::extract_player_data(&game, port) else {
+            continue;
+        };
+        let hits = sdi::player_hits(&player_data, &Ruleset::default()).expect("Replay records hitlag");
+        assert!(!sdi::check_distance(&hits).result, "port {} flagged", port);
+    }
+}
+
+#[test]
+fn test_sdi_incidents() {
+    let data = read_slp_file("nonlegal/digital/sdi/sdi_unnerfed.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+    let player_data = parser::extract_player_data(&game, 3).unwrap();
+
+    // Three overlapping rule #3 matches from one alternation become a single incident
+    let incidents = sdi::player_incidents(&player_data, &Ruleset::default());
+    assert_eq!(incidents.len(), 2);
+    assert_eq!(incidents[0].start_frame, 34);
+    assert_eq!(incidents[1].rules, vec![3]);
+    assert_eq!(incidents[1].count, 3);
+
+    let raw = sdi::check_player(&player_data, &Ruleset::default());
+    assert_eq!(incidents.iter().map(|i| i.count).sum::<usize>(), raw.details.len());
+
+    let grouped = sdi::check_incidents(&incidents, &player_data.main_coords);
+    assert_eq!(grouped.details.len(), 2);
+    assert_eq!(grouped.details[1].evidence.len(), 6);
+}
+
+#[test]
+fn test_region_trace() {
+    let data = read_slp_file("nonlegal/digital/sdi/sdi_tas_neutral_cardinal.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+    let player_data = parser::extract_player_data(&game, 3).unwrap();
+
+    // First hit: hitlag on frames 21-24, tapping left twice
+    let trace = sdi::trace(&player_data, &Ruleset::default(), Some(20), Some(25));
+    assert_eq!(trace.len(), 6);
+    assert_eq!(trace[0].frame, 20);
+    assert!(!trace[0].sdi_frame);
+    assert_eq!(trace[1].events[0], SdiTraceEvent::HitlagStart);
+    assert!(trace[1].events.contains(&SdiTraceEvent::RuleViolation { rule: 1 }));
+    assert_eq!(trace[2].sdi_region, SDIRegion::W);
+    assert_eq!(trace[2].joystick_region, JoystickRegion::W);
+    assert_eq!(
+        trace[2].events,
+        vec![SdiTraceEvent::SdiInput { from: SDIRegion::DZ, to: SDIRegion::W }]
+    );
+    assert!(trace[4].events.contains(&SdiTraceEvent::HitlagEnd));
+
+    let inputs = trace
+        .iter()
+        .flat_map(|frame| &frame.events)
+        .filter(|event| matches!(event, SdiTraceEvent::SdiInput { .. }))
+        .count();
+    assert_eq!(inputs, 2);
+}
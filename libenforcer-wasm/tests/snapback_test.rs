@@ -0,0 +1,54 @@
+//! Integration tests for snapback filter detection
+//!
+//! Test data:
+//!   nonlegal/analog/goomwave/ — Port 1 GoomWave (filtered)
+//!   legal/analog/traveltime/  — Ports 2 and 3 unmodified analog
+
+#[path = "common/mod.rs"]
+mod common;
+
+#[cfg(not(target_arch = "wasm32"))]
+use libenforcer_wasm::{checks::snapback, parser, ruleset::Ruleset, types::SnapbackFilter};
+use peppi::io::slippi::de::read as read_slippi;
+use std::io::Cursor;
+
+use common::*;
+
+#[test]
+fn test_snapback_goomwave_filtered() {
+    for file in [
+        "nonlegal/analog/goomwave/Game_20250216T194607.slp",
+        "nonlegal/analog/goomwave/Game_20250216T194746.slp",
+    ] {
+        let data = read_slp_file(file);
+        let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+        let player_data = parser::extract_player_data(&game, 1).unwrap();
+
+        let analysis = snapback::analyze(&player_data.raw_joystick_coords, &Ruleset::default());
+        assert_eq!(analysis.filter, SnapbackFilter::Aggressive, "{} should filter snapback", file);
+        assert!(analysis.strength > 0.8);
+    }
+}
+
+#[test]
+fn test_snapback_legal_analog_unfiltered() {
+    let files = read_slp_dir("legal/analog/traveltime");
+    assert!(!files.is_empty(), "Should find traveltime replay files");
+
+    for (filename, data) in &files {
+        let game = read_slippi(&mut Cursor::new(data), None).unwrap();
+        for port in [2, 3] {
+            let player_data = parser::extract_player_data(&game, port).unwrap();
+            let analysis = snapback::analyze(&player_data.raw_joystick_coords, &Ruleset::default());
+            assert_eq!(
+                analysis.filter,
+                SnapbackFilter::Unfiltered,
+                "{} port {} should rebound naturally (rate {:.2})",
+                filename,
+                port,
+                analysis.rebound_rate
+            );
+            assert!(analysis.releases >= 100);
+        }
+    }
+}
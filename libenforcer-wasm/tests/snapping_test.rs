@@ -0,0 +1,53 @@
+//! Integration tests for analog snapping detection
+//!
+//! Test data:
+//!   nonlegal/analog/goomwave/ — Port 1 GoomWave
+//!   legal/analog/traveltime/  — Ports 2 and 3 unmodified analog
+
+#[path = "common/mod.rs"]
+mod common;
+
+#[cfg(not(target_arch = "wasm32"))]
+use libenforcer_wasm::{checks::snapping, parser, ruleset::Ruleset, types::SnapKind};
+use peppi::io::slippi::de::read as read_slippi;
+use std::io::Cursor;
+
+use common::*;
+
+#[test]
+fn test_snapping_goomwave_cardinals() {
+    for file in [
+        "nonlegal/analog/goomwave/Game_20250216T194607.slp",
+        "nonlegal/analog/goomwave/Game_20250216T194746.slp",
+    ] {
+        let data = read_slp_file(file);
+        let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+        let player_data = parser::extract_player_data(&game, 1).unwrap();
+
+        let analysis = snapping::analyze(&player_data.main_coords, &Ruleset::default());
+        assert!(analysis.score > 0.99, "{} should snap fully, got {}", file, analysis.score);
+        assert!(!analysis.axes.is_empty());
+        assert!(analysis.axes.iter().all(|a| a.kind == SnapKind::Cardinal));
+        assert!(analysis.axes.iter().all(|a| !a.frames.is_empty()));
+    }
+}
+
+#[test]
+fn test_snapping_legal_analog() {
+    let files = read_slp_dir("legal/analog/traveltime");
+    assert!(!files.is_empty(), "Should find traveltime replay files");
+
+    let strict = Ruleset {
+        max_snapping_score: Some(0.6),
+        ..Ruleset::default()
+    };
+    for (filename, data) in &files {
+        let game = read_slippi(&mut Cursor::new(data), None).unwrap();
+        for port in [2, 3] {
+            let player_data = parser::extract_player_data(&game, port).unwrap();
+            let analysis = snapping::analyze(&player_data.main_coords, &strict);
+            assert!(analysis.pass, "{} port {} should not snap", filename, port);
+            assert!(analysis.axes.is_empty());
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! Integration tests for per-team summaries
+
+#[path = "common/mod.rs"]
+mod common;
+
+#[cfg(not(target_arch = "wasm32"))]
+use libenforcer_wasm::{
+    checks, parser, teams,
+    types::{ControllerType, PlayerAnalysis},
+};
+use peppi::io::slippi::de::read as read_slippi;
+use std::io::Cursor;
+
+use common::*;
+
+/// Blue team (ports 1-2) against green team (ports 3-4)
+#[cfg(not(target_arch = "wasm32"))]
+fn analyze_doubles() -> Vec<(u8, PlayerAnalysis)> {
+    let data = read_slp_file("handwarmers/Game_8C56C529AEAA_20231021T125730.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+    (0..4)
+        .map(|port| {
+            let player_data = parser::extract_player_data(&game, port).unwrap();
+            (port as u8, checks::analyze_player(&player_data))
+        })
+        .collect()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_team_summary() {
+    let analyses = analyze_doubles();
+    let teams: Vec<Option<u8>> = analyses.iter().map(|(_, a)| a.team).collect();
+    assert_eq!(teams, vec![Some(1), Some(1), Some(2), Some(2)]);
+
+    let players: Vec<(u8, &PlayerAnalysis)> = analyses.iter().map(|(port, a)| (*port, a)).collect();
+    let summaries = teams::summarize(&players);
+    assert_eq!(summaries.len(), 2);
+    assert_eq!(summaries[0].team, 1);
+    let ports: Vec<u8> = summaries[0].members.iter().map(|m| m.port).collect();
+    assert_eq!(ports, vec![0, 1]);
+    assert_eq!(
+        summaries[0].is_legal,
+        analyses[0].1.is_legal && analyses[1].1.is_legal
+    );
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_team_mixed_controller_types() {
+    let mut analyses = analyze_doubles();
+    analyses[2].1.controller_type = ControllerType::Box;
+    analyses[3].1.controller_type = ControllerType::Analog;
+
+    let players: Vec<(u8, &PlayerAnalysis)> = analyses.iter().map(|(port, a)| (*port, a)).collect();
+    let summaries = teams::summarize(&players);
+    assert!(summaries[1].mixed_controller_types);
+    assert_eq!(summaries[1].members[0].controller_type, ControllerType::Box);
+}
@@ -0,0 +1,60 @@
+//! Integration tests for minimum transition time rules
+
+#[path = "common/mod.rs"]
+mod common;
+
+#[cfg(not(target_arch = "wasm32"))]
+use libenforcer_wasm::{checks::{crouch_uptilt, transitions}, parser, ruleset::Ruleset, types::ControllerType};
+use peppi::io::slippi::de::read as read_slippi;
+use std::io::Cursor;
+
+use common::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_transitions_match_crouch_uptilt() {
+    let data = read_slp_file("nonlegal/digital/crouch_uptilt/crouch_uptilt_unnerfed.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+    let player_data = parser::extract_player_data(&game, 3).unwrap();
+
+    let analysis = transitions::analyze(&player_data, ControllerType::Box, &Ruleset::default());
+    assert!(!analysis.pass);
+
+    let uptilt = analysis.rules.iter().find(|r| r.name == "Crouch-uptilt").unwrap();
+    assert_eq!(uptilt.violations.len(), 6);
+    assert!(uptilt.violations[0].reason.contains("SquatWait at frame"));
+    assert!(uptilt.violations[0].reason.contains("AttackHi3 at frame"));
+    let upsmash = analysis.rules.iter().find(|r| r.name == "Crouch-upsmash").unwrap();
+    assert!(upsmash.pass);
+
+    let view = crouch_uptilt::from_transitions(&analysis);
+    let direct = crouch_uptilt::check(&player_data.main_coords, &player_data.action_states);
+    assert!(view.result);
+    assert_eq!(view.details.len(), direct.details.len());
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_transitions_unnerfed_tap_jump() {
+    // Unnerfed box goes from crouch to tap jump without a neutral frame
+    let data = read_slp_file("nonlegal/digital/crouch_uptilt/crouch_uptilt_unnerfed.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+    let player_data = parser::extract_player_data(&game, 3).unwrap();
+
+    let analysis = transitions::analyze(&player_data, ControllerType::Box, &Ruleset::default());
+    let tap_jump = analysis.rules.iter().find(|r| r.name == "Crouch-tap-jump").unwrap();
+    assert!(!tap_jump.pass);
+    assert!(tap_jump.violations[0].reason.contains("KneeBend at frame"));
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_transitions_legal_cstick_upsmash() {
+    // Crouch into a C-stick upsmash on the next frame
+    let data = read_slp_file("legal/digital/carvac_23.1/Game_20260203T231033.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+    let player_data = parser::extract_player_data(&game, 0).unwrap();
+
+    let analysis = transitions::analyze(&player_data, ControllerType::Box, &Ruleset::default());
+    assert!(analysis.pass, "C-stick upsmash out of crouch is legal");
+}
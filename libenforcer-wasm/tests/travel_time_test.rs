@@ -42,8 +42,8 @@ fn test_average_travel_time_from_legal_digital_file() {
         let player_data = parser::extract_player_data(&game, 2).unwrap();
 
         let result = travel_time::check(&player_data.main_coords);
-        assert_eq!(
-            result.result, false,
+        assert!(
+            !result.result,
             "Potion {} should pass travel time check",
             i
         );
@@ -67,8 +67,8 @@ fn test_average_travel_time_from_legal_analog_file() {
         let player_data = parser::extract_player_data(&game, 2).unwrap();
 
         let result = travel_time::check(&player_data.main_coords);
-        assert_eq!(
-            result.result, false,
+        assert!(
+            !result.result,
             "{} should pass travel time check",
             filename
         );
@@ -92,8 +92,8 @@ fn test_average_travel_time_from_nonlegal_digital_file() {
         let player_data = parser::extract_player_data(&game, 3).unwrap();
 
         let result = travel_time::check(&player_data.main_coords);
-        assert_eq!(
-            result.result, true,
+        assert!(
+            result.result,
             "{} should fail travel time check",
             filename
         );
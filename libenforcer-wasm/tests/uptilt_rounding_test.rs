@@ -23,8 +23,8 @@ fn test_uptilt_rounding_legal_analog_a() {
         // Test port 3 (player index 2)
         if let Some(player_data) = parser::extract_player_data(&game, 2) {
             let result = uptilt_rounding::check(&player_data.main_coords);
-            assert_eq!(
-                result.result, false,
+            assert!(
+                !result.result,
                 "{} port 3 should pass uptilt rounding check",
                 filename
             );
@@ -33,8 +33,8 @@ fn test_uptilt_rounding_legal_analog_a() {
         // Test port 4 (player index 3)
         if let Some(player_data) = parser::extract_player_data(&game, 3) {
             let result = uptilt_rounding::check(&player_data.main_coords);
-            assert_eq!(
-                result.result, false,
+            assert!(
+                !result.result,
                 "{} port 4 should pass uptilt rounding check",
                 filename
             );
@@ -51,12 +51,12 @@ fn test_uptilt_rounding_nonlegal_analog() {
     let player_data = parser::extract_player_data(&game, 0).unwrap();
 
     let result = uptilt_rounding::get_uptilt_check(&player_data.main_coords);
-    assert_eq!(
-        result.result, true,
+    assert!(
+        result.result,
         "goomwave_uptilt_p1.slp should fail uptilt rounding check"
     );
     assert!(
-        result.details.len() >= 1,
+        !result.details.is_empty(),
         "Should have at least 1 violation, got {}",
         result.details.len()
     );
@@ -69,8 +69,8 @@ fn test_uptilt_rounding_legal_analog_b() {
     let player_data = parser::extract_player_data(&game, 0).unwrap();
 
     let result = uptilt_rounding::check(&player_data.main_coords);
-    assert_eq!(
-        result.result, false,
+    assert!(
+        !result.result,
         "Game_20250107T140347.slp should pass uptilt rounding check"
     );
     assert_eq!(result.details.len(), 0, "Should have 0 violations");
@@ -83,8 +83,8 @@ fn test_uptilt_rounding_legal_analog_c() {
     let player_data = parser::extract_player_data(&game, 0).unwrap();
 
     let result = uptilt_rounding::check(&player_data.main_coords);
-    assert_eq!(
-        result.result, false,
+    assert!(
+        !result.result,
         "Game_20250107T142211.slp should pass uptilt rounding check"
     );
     assert_eq!(result.details.len(), 0, "Should have 0 violations");
@@ -97,8 +97,8 @@ fn test_uptilt_rounding_legal_analog_d() {
     let player_data = parser::extract_player_data(&game, 0).unwrap();
 
     let result = uptilt_rounding::check(&player_data.main_coords);
-    assert_eq!(
-        result.result, false,
+    assert!(
+        !result.result,
         "Game_20250123T212056.slp should pass uptilt rounding check"
     );
     assert_eq!(result.details.len(), 0, "Should have 0 violations");
@@ -6,7 +6,11 @@
 mod common;
 
 use common::*;
-use libenforcer_wasm::{parser, types, types::Coord, utils};
+use libenforcer_wasm::{
+    parser,
+    types::{Coord, FrameStatus, StickStream},
+    utils,
+};
 use peppi::game::Game;
 use peppi::io::slippi::de::read as read_slippi;
 use std::io::Cursor;
@@ -14,10 +18,10 @@ use std::io::Cursor;
 #[cfg(not(target_arch = "wasm32"))]
 #[test]
 fn test_float_equals_allow_wiggle_room() {
-    assert_eq!(utils::float_equals(0.8, 0.8), true);
-    assert_eq!(utils::float_equals(0.8, 0.7999), true);
-    assert_eq!(utils::float_equals(-0.7, -0.7000000000001), true);
-    assert_eq!(utils::float_equals(0.8, -0.8), false);
+    assert!(utils::float_equals(0.8, 0.8));
+    assert!(utils::float_equals(0.8, 0.7999));
+    assert!(utils::float_equals(-0.7, -0.7000000000001));
+    assert!(!utils::float_equals(0.8, -0.8));
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -77,9 +81,8 @@ fn test_is_box_inputs_pikachu_game() {
     let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
     let player_data = parser::extract_player_data(&game, 1).unwrap();
 
-    assert_eq!(
+    assert!(
         utils::is_box_controller(&player_data.main_coords),
-        true,
         "Pikachu game should be detected as box controller"
     );
 }
@@ -97,9 +100,8 @@ fn test_is_box_inputs() {
         let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
         let player_data = parser::extract_player_data(&game, 0).unwrap();
 
-        assert_eq!(
+        assert!(
             utils::is_box_controller(&player_data.main_coords),
-            true,
             "Game {} should be detected as box controller",
             i
         );
@@ -111,14 +113,13 @@ fn test_is_box_inputs() {
     let player_data = parser::extract_player_data(&game, 3).unwrap();
 
     assert!(
-        player_data.main_coords.len() > 0,
+        !player_data.main_coords.is_empty(),
         "Should have coordinate data"
     );
     let unique = utils::get_unique_coords(&player_data.main_coords);
     assert!(unique.len() > 13, "GCC should have many unique coordinates");
-    assert_eq!(
-        utils::is_box_controller(&player_data.main_coords),
-        false,
+    assert!(
+        !utils::is_box_controller(&player_data.main_coords),
         "GCC player should not be detected as box"
     );
 }
@@ -132,9 +133,8 @@ fn test_is_box_inputs_potion_dataset() {
         let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
         let player_data = parser::extract_player_data(&game, 2).unwrap();
 
-        assert_eq!(
+        assert!(
             utils::is_box_controller(&player_data.main_coords),
-            true,
             "Potion {} should be detected as box controller",
             i
         );
@@ -148,9 +148,8 @@ fn test_is_box_inputs_orca_dataset_a() {
     let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
     let player_data = parser::extract_player_data(&game, 0).unwrap();
 
-    assert_eq!(
-        utils::is_box_controller(&player_data.main_coords),
-        false,
+    assert!(
+        !utils::is_box_controller(&player_data.main_coords),
         "Orca dataset A should not be detected as box"
     );
 }
@@ -162,9 +161,8 @@ fn test_is_box_inputs_orca_dataset_b() {
     let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
     let player_data = parser::extract_player_data(&game, 0).unwrap();
 
-    assert_eq!(
-        utils::is_box_controller(&player_data.main_coords),
-        false,
+    assert!(
+        !utils::is_box_controller(&player_data.main_coords),
         "Orca dataset B should not be detected as box"
     );
 }
@@ -176,9 +174,8 @@ fn test_is_box_inputs_orca_dataset_c() {
     let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
     let player_data = parser::extract_player_data(&game, 0).unwrap();
 
-    assert_eq!(
-        utils::is_box_controller(&player_data.main_coords),
-        false,
+    assert!(
+        !utils::is_box_controller(&player_data.main_coords),
         "Orca dataset C should not be detected as box"
     );
 }
@@ -190,9 +187,8 @@ fn test_is_box_inputs_orca_dataset_d() {
     let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
     let player_data = parser::extract_player_data(&game, 0).unwrap();
 
-    assert_eq!(
-        utils::is_box_controller(&player_data.main_coords),
-        false,
+    assert!(
+        !utils::is_box_controller(&player_data.main_coords),
         "Orca dataset D should not be detected as box"
     );
 }
@@ -204,9 +200,8 @@ fn test_is_box_inputs_orca_dataset_e() {
     let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
     let player_data = parser::extract_player_data(&game, 0).unwrap();
 
-    assert_eq!(
-        utils::is_box_controller(&player_data.main_coords),
-        false,
+    assert!(
+        !utils::is_box_controller(&player_data.main_coords),
         "Orca dataset E should not be detected as box"
     );
 }
@@ -218,9 +213,8 @@ fn test_is_box_inputs_xbox_controller_a() {
     let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
     let player_data = parser::extract_player_data(&game, 1).unwrap();
 
-    assert_eq!(
-        utils::is_box_controller(&player_data.main_coords),
-        false,
+    assert!(
+        !utils::is_box_controller(&player_data.main_coords),
         "Xbox controller A should not be detected as box"
     );
 }
@@ -232,9 +226,8 @@ fn test_is_box_inputs_xbox_controller_b() {
     let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
     let player_data = parser::extract_player_data(&game, 1).unwrap();
 
-    assert_eq!(
-        utils::is_box_controller(&player_data.main_coords),
-        false,
+    assert!(
+        !utils::is_box_controller(&player_data.main_coords),
         "Xbox controller B should not be detected as box"
     );
 }
@@ -327,3 +320,61 @@ fn test_process_main_stick_inputs() {
         }
     }
 }
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_stick_streams() {
+    let data = read_slp_file("legal/analog/orca/Game_20241228T180350.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+    let player_data = parser::extract_player_data(&game, 0).unwrap();
+    let streams = player_data.stick_streams();
+
+    // Modern replays record raw values, so nothing is approximated
+    for stream in [&streams.raw, &streams.engine, &streams.processed] {
+        assert!(!stream.approximated_x && !stream.approximated_y);
+    }
+    assert_eq!(streams.raw.coords.len(), streams.engine.coords.len());
+    assert_eq!(streams.raw.coords.len(), streams.processed.coords.len());
+
+    // Raw values are int8, engine and processed values are normalized
+    assert!(streams.raw.coords.iter().any(|c| c.x.abs() > 1.0));
+    assert!(streams.engine.coords.iter().all(|c| c.x.abs() <= 1.0 && c.y.abs() <= 1.0));
+    assert_eq!(player_data.stick(StickStream::Processed), player_data.main_coords.as_slice());
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_rollback_frames_discarded() {
+    // Netplay replay where 25 frames were re-simulated by rollbacks
+    let data = read_slp_file("legal/digital/carvac_23.1/Game_20260203T231033.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+    let player_data = parser::extract_player_data(&game, 0).unwrap();
+
+    assert_eq!(player_data.rollback_frames, 25);
+    assert_eq!(player_data.main_coords.len(), game.len() - 25);
+
+    // One entry per frame id, in order
+    let frames = parser::finalized_frames(&game);
+    let ids: Vec<i32> = frames.iter().map(|&i| game.frame(i).id).collect();
+    assert!(ids.windows(2).all(|w| w[1] == w[0] + 1));
+    assert_eq!(ids[0], -123);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_frame_status() {
+    let data = read_slp_file("legal/digital/carvac_23.1/Game_20260203T231033.slp");
+    let game = read_slippi(&mut Cursor::new(&data), None).unwrap();
+    let player_data = parser::extract_player_data(&game, 0).unwrap();
+    let status = |frame: i32| player_data.frame_status[(frame + 123) as usize];
+
+    assert_eq!(player_data.frame_status.len(), player_data.main_coords.len());
+    assert_eq!(status(2027), FrameStatus::Active);
+    // First stock lost on frame 2028, then the respawn platform from 2088
+    assert_eq!(status(2028), FrameStatus::Dead);
+    assert_eq!(status(2088), FrameStatus::RespawnInvincible);
+    // Invincibility outlasts the platform until the hurtbox turns vulnerable
+    assert_eq!(status(2200), FrameStatus::RespawnInvincible);
+    assert_eq!(status(2268), FrameStatus::Active);
+    assert!(!player_data.frame_status.contains(&FrameStatus::Absent));
+}
@@ -14,11 +14,13 @@ import wasmInit, {
   average_travel_coord_hit_rate,
   has_goomwave_clamping,
   get_joystick_region,
+  get_action_state_name,
   process_analog_stick,
   float_equals,
   is_equal,
   get_unique_coords,
   get_target_coords,
+  get_coord_inventory,
   SlpGame,
 } from '../pkg/web/libenforcer_wasm.js'
 
@@ -58,21 +60,403 @@ export type FuzzAnalysis = {
   violations: Violation[]
 }
 
+/** SOCD (simultaneous opposite cardinal direction) resolution behavior */
+export type SocdMode = "LastInputWins" | "SecondInputPriority" | "Neutral" | "Unknown"
+
+/** SOCD behavior observed on a single stick axis */
+export type SocdAxisAnalysis = {
+  mode: SocdMode
+  direct_flips: number
+  neutral_crossings: number
+  reactivations: number
+  releases_to_neutral: number
+}
+
+/** SOCD resolution analysis for a box controller */
+export type SocdAnalysis = {
+  pass: boolean
+  horizontal: SocdAxisAnalysis
+  vertical: SocdAxisAnalysis
+  violations: Violation[]
+}
+
+/** A modifier notch: one output coordinate a box lands on, fuzz offsets folded in */
+export type CoordNotch = {
+  coord: Coord
+  angle: number      // degrees, counter-clockwise from +X
+  magnitude: number
+  hold_count: number
+  frames: number[]   // game frame each hold started on
+}
+
+/** Inventory of held coordinates, validated against the ruleset whitelist */
+export type CoordInventory = {
+  pass: boolean
+  notches: CoordNotch[]
+  violations: Violation[]
+}
+
+/** How well a player's outputs match one box modifier layout family (not a firmware version) */
+export type FirmwareMatch = {
+  name: string
+  score: number            // 0.0 - 1.0
+  explained: number        // share of holds landing on the profile's notches
+  coverage: number         // share of the profile's notches used
+  fuzz_consistent: boolean | null
+  unexplained_coords: Coord[]
+}
+
+/** C-stick analysis for a box controller */
+export type CStickAnalysis = {
+  pass: boolean
+  notches: CoordNotch[]
+  travel_rate: number      // informational unless the ruleset sets min_cstick_travel_rate
+  analog_modifier: boolean // lands on enough angles to be an analog C-stick modifier
+  violations: Violation[]
+}
+
+/** Layout family fingerprint for a box controller player (best match first) */
+export type FirmwareFingerprint = {
+  matches: FirmwareMatch[]
+  tied: string[]           // profiles scoring the same as the best match, including it
+}
+
+/** Physical gate shape of an analog stick */
+export type GateShape = "Octagon" | "Circle" | "Unknown"
+
+/** An angle where an analog stick rests on its rim */
+export type AnalogNotch = {
+  angle: number            // degrees, 0-360
+  coord: Coord             // modal raw coordinate
+  hold_count: number
+  distinct_coords: number
+  concentration: number    // share of holds on the modal coordinate
+  remapped: boolean        // snaps to one exact value (firmware notch remapping)
+}
+
+/** Analog stick gate and notch calibration analysis */
+export type AnalogCalibration = {
+  pass: boolean
+  gate_shape: GateShape
+  gate_radii: number[]     // max raw magnitude per 5° bin
+  notches: AnalogNotch[]
+  violations: Violation[]
+}
+
+export type SnapKind = "Cardinal" | "Diagonal" | "Arbitrary"
+
+/** How strongly stick coordinates collapse onto one axis */
+export type SnapAxis = {
+  angle: number            // degrees, 0-360
+  kind: SnapKind
+  on_axis: number          // coordinates within half a raw unit of the axis
+  near_axis: number        // coordinates in the band beside the axis
+  score: number            // on_axis / (on_axis + near_axis)
+  frames: number[]         // first frames on the axis
+}
+
+/** Analog snapping analysis (e.g. GoomWave cardinal snapping) */
+export type SnappingAnalysis = {
+  pass: boolean
+  score: number            // highest axis score, 0-1
+  axes: SnapAxis[]         // axes judged to snap
+  violations: Violation[]
+}
+
+/** How strongly an analog stick's release rebound is filtered */
+export type SnapbackFilter = "Unfiltered" | "Light" | "Aggressive" | "Unknown"
+
+/** Main stick release (snapback) analysis */
+export type SnapbackAnalysis = {
+  pass: boolean
+  filter: SnapbackFilter
+  strength: number         // 0 (natural rebound) to 1 (no rebound)
+  releases: number         // smash inputs released through neutral
+  rebounds: number         // releases with a small overshoot past neutral
+  rebound_rate: number
+  mean_rebound: number     // raw units
+  deadzone_crossings: number
+  violations: Violation[]
+}
+
+export type RemapAxis = "X" | "Y" | "Magnitude"
+
+/** A range of raw values (inclusive) the stick never outputs */
+export type RemapGap = {
+  axis: RemapAxis
+  start: number
+  end: number
+}
+
+/** Raw stick values (inclusive), compared by absolute value */
+export type StickRegion = {
+  x_min: number
+  x_max: number
+  y_min: number
+  y_max: number
+}
+
+/** Analog curve remapping (ESS-style) analysis */
+export type RemapAnalysis = {
+  pass: boolean
+  dense_values: number     // X/Y values with enough samples to judge
+  gap_values: number       // of those, never output
+  gap_fraction: number
+  gaps: RemapGap[]
+  forbidden_region_frames: number  // frames outputting a ruleset forbidden region
+  violations: Violation[]
+}
+
+/** SDI inputs during one hitlag window the player was hit in */
+export type SdiHit = {
+  start_frame: number      // first hitlag frame
+  end_frame: number        // last hitlag frame (inclusive)
+  sdi_inputs: number       // frames the stick entered a new cardinal/diagonal
+  displacement: number     // game units moved during hitlag
+  max_legal_displacement: number
+  outlier: boolean         // displacement exceeds the legal maximum (ignores ASDI, hitbox SDI multipliers, platforms)
+}
+
+/** Overlapping SDI rule violations merged into one incident */
+export type SdiIncident = {
+  start_frame: number
+  end_frame: number        // inclusive
+  rules: number[]          // SDI rules (1-3) triggered
+  count: number            // raw rule violations merged
+}
+
+export type SdiRegionName = "DZ" | "NE" | "SE" | "SW" | "NW" | "N" | "E" | "S" | "W" | "TILT"
+
+/** Something the SDI engine saw on one frame */
+export type SdiTraceEvent =
+  | { kind: "HitlagStart" }
+  | { kind: "HitlagEnd" }
+  | { kind: "SdiInput", from: SdiRegionName, to: SdiRegionName }
+  | { kind: "RuleViolation", rule: number }  // a rule's window starting on this frame failed
+
+/** Stick regions and SDI events for one frame, from getRegionTrace() */
+export type RegionTraceFrame = {
+  frame: number
+  coord: Coord
+  sdi_region: SdiRegionName
+  joystick_region: Exclude<SdiRegionName, "TILT">
+  sdi_frame: boolean       // SDI possible on this frame (always true without hitlag data)
+  events: SdiTraceEvent[]
+}
+
+/** Whether a player was in play on a frame, from getFrameStatus() */
+export type FrameStatus = "Active" | "Absent" | "Dead" | "RespawnInvincible"
+
+/** One main stick stream, from getStickStreams() */
+export type StickStreamData = {
+  coords: Coord[]
+  approximated_x: boolean  // X rebuilt from the engine stream on some frames (pre Slippi v1.2)
+  approximated_y: boolean  // Y rebuilt from the engine stream on some frames (pre Slippi v3.15)
+}
+
+/** Main stick data as raw int8 values, the engine's pre.joystick and this library's processed coords */
+export type StickStreams = {
+  raw: StickStreamData
+  engine: StickStreamData
+  processed: StickStreamData
+}
+
+/** Minimum time between leaving one set of action states and entering another */
+export type TransitionRule = {
+  name: string
+  from_states: number[]
+  to_states: number[]
+  min_frames: number
+  controller_types: ControllerType[]
+  main_stick_only?: boolean  // skip transitions entered with the C-stick
+  min_stick_y?: number | null  // only transitions entered with the main stick this far up (e.g. tap jump)
+}
+
+export type TransitionRuleResult = {
+  name: string
+  pass: boolean
+  violations: Violation[]
+}
+
+/** Minimum transition time analysis, one result per applicable rule */
+export type TransitionAnalysis = {
+  pass: boolean
+  rules: TransitionRuleResult[]
+}
+
+/** Stick thresholds for region classification */
+export type RegionGeometry = {
+  deadzone: number
+  inclusive_deadzone: boolean  // an axis exactly on the deadzone bound is inside it
+  min_magnitude: number        // below this (but outside the deadzone) is tilt
+}
+
+/** Ruleset overrides passed to analyzePlayer(). Omitted fields use the defaults. */
+export type Ruleset = {
+  allowed_socd_horizontal?: SocdMode[]
+  allowed_socd_vertical?: SocdMode[]
+  require_socd_mode?: boolean        // fail boxes whose inferred SOCD mode isn't allowed
+  allowed_coordinates?: Coord[] | null  // compared by absolute value
+  max_remapped_notches?: number | null
+  max_snapping_score?: number | null
+  min_remap_gap_values?: number       // never-output raw X/Y values before a curve is remapped, default 8
+  min_remap_gap_fraction?: number     // share of judged values that must be gaps, default 0.05
+  forbidden_stick_regions?: StickRegion[]  // raw regions an analog stick may not output
+  allowed_snapback_filters?: SnapbackFilter[]
+  transition_rules?: TransitionRule[]  // replaces the default rules
+  forbidden_cstick_x?: number[]       // compared by absolute value, default [0.8, 0.6625]
+  forbidden_cstick_y?: number[]       // compared by absolute value, default [0.8, 0.6625]
+  allowed_cstick_angles?: number[] | null  // degrees, folded into one quadrant; cardinals always allowed
+  min_cstick_travel_rate?: number | null
+  require_trigger_fuzzing?: boolean  // fail boxes whose analog triggers aren't fuzzed
+  sdi_raw_violations?: boolean  // one SDI violation per rule match instead of per incident
+  require_sdi_distance?: boolean  // fail hits displaced further than legal SDI allows (rough model, off by default)
+  sdi_regions?: RegionGeometry  // default { deadzone: 0.2875, inclusive_deadzone: true, min_magnitude: 0.7 }
+  fail_on_degraded_data?: boolean  // let checks on approximated/incomplete data fail the player
+}
+
+export type CheckFidelity = "Full" | "Degraded" | "Skipped"
+
+/** A check that can fail a player, named after its PlayerAnalysis field */
+export type CheckId =
+  | "travel_time" | "disallowed_cstick" | "cstick" | "crouch_uptilt" | "sdi" | "sdi_distance"
+  | "input_fuzzing" | "cstick_fuzzing" | "l_trigger_fuzzing" | "r_trigger_fuzzing" | "socd"
+  | "coord_inventory" | "goomwave" | "uptilt_rounding" | "analog_calibration" | "snapping"
+  | "snapback" | "remapping" | "transitions"
+
+/** How faithfully one check could run */
+export type CheckQuality = {
+  check: CheckId
+  fidelity: CheckFidelity
+  reasons: string[]
+}
+
+/** What data a player's analysis had to work with */
+export type DataQuality = {
+  slippi_version: string            // e.g. "3.16.0"
+  missing_fields: string[]          // e.g. "raw_analog_y", "hitlag"
+  rollback_frames: number           // rolled-back frame copies discarded (netplay), only finalized frames are checked
+  approximated_streams: ("Raw" | "Engine" | "Processed")[]
+  checks: CheckQuality[]
+}
+
 /** Full analysis results for a single player */
 export type PlayerAnalysis = {
   controller_type: ControllerType
+  team: number | null            // team color in team games
   is_legal: boolean
 
   // Box controller checks (undefined if analog)
   travel_time?: CheckResult
-  disallowed_cstick?: CheckResult
-  crouch_uptilt?: CheckResult
-  sdi?: CheckResult
+  disallowed_cstick?: CheckResult  // informational; cstick fails forbidden_cstick_x/y values
+  cstick?: CStickAnalysis
+  crouch_uptilt?: CheckResult  // view of the Crouch-uptilt transition rule
+  sdi?: CheckResult              // one violation per incident; restricted to hitlag on Slippi v3.8+ replays; metric is the game frame the pattern starts on
+  sdi_incidents?: SdiIncident[]
+  sdi_hits?: SdiHit[]            // undefined if the replay has no hitlag data
+  sdi_distance?: CheckResult     // hits displaced further than legal SDI allows; fails only with require_sdi_distance
   input_fuzzing?: FuzzAnalysis
+  cstick_fuzzing?: FuzzAnalysis
+  l_trigger_fuzzing?: FuzzAnalysis  // only affects is_legal with require_trigger_fuzzing
+  r_trigger_fuzzing?: FuzzAnalysis
+  socd?: SocdAnalysis               // only affects is_legal with require_socd_mode
+  coord_inventory?: CoordInventory
+  firmware?: FirmwareFingerprint
 
   // Analog controller checks (undefined if box)
   goomwave?: CheckResult
   uptilt_rounding?: CheckResult
+  analog_calibration?: AnalogCalibration
+  snapping?: SnappingAnalysis
+  snapback?: SnapbackAnalysis
+  remapping?: RemapAnalysis
+
+  // Both controller types
+  transitions: TransitionAnalysis
+
+  data_quality: DataQuality      // degraded checks only affect is_legal with fail_on_degraded_data
+}
+
+/** Verdicts of every player on one team, from analyzeTeams() */
+export type TeamSummary = {
+  team: number                      // team color (0 red, 1 blue, 2 green)
+  members: { port: number, controller_type: ControllerType, is_legal: boolean }[]
+  is_legal: boolean                 // every member passed
+  mixed_controller_types: boolean   // teammates use different controller types
+}
+
+export type GameMode = "Offline" | "OnlineRanked" | "OnlineUnranked" | "OnlineDirect" | "OnlineTeams" | "Online"
+
+/** Per-player settings and identity, from getMetadata() */
+export type PlayerMetadata = {
+  port: number
+  character_id: number
+  player_type: number              // 0 human, 1 CPU, 2 demo
+  character_color: number
+  team: number | null              // team color in team games
+  stocks: number
+  name_tag: string | null
+  netplay_name: string | null      // Slippi v3.9+
+  connect_code: string | null
+  slippi_uid: string | null
+}
+
+/** Everything a replay records about its game besides the frames, from getMetadata() */
+export type GameMetadata = {
+  start_at: string | null          // ISO 8601
+  played_on: string | null         // "dolphin", "nintendont" or "network"
+  console_nick: string | null
+  slippi_version: string
+  is_pal: boolean | null
+  is_frozen_ps: boolean | null
+  game_mode: GameMode
+  is_teams: boolean
+  stage_id: number
+  timer_type: "None" | "Decreasing" | "Increasing"
+  timer_seconds: number
+  match_id: string | null          // Slippi Online only (v3.14+)
+  game_number: number | null
+  tiebreaker_number: number | null
+  players: PlayerMetadata[]
+}
+
+/** How a game was decided, from getGameOutcome() */
+export type GameOutcome = {
+  end_method: "Unresolved" | "Time" | "Game" | "Resolved" | "NoContest" | null  // null without an end block
+  lras_initiator: number | null  // port that quit out
+  players: {
+    port: number
+    placement: number | null     // 0 is the winner (Slippi v3.13+)
+    stocks: number               // on the last frame
+    percent: number
+  }[]
+}
+
+/** Handwarmer verdict and evidence, from getHandwarmerAnalysis() */
+export type HandwarmerAnalysis = {
+  is_handwarmer: boolean
+  rule: "ShortGame" | "IdleInDeadzone" | null
+  port: number | null         // player responsible, null for game-wide rules
+  start_frame: number | null  // game frames the rule triggered over, inclusive
+  end_frame: number | null
+  signals: {                  // informational, don't affect is_handwarmer
+    frames: number
+    no_damage_dealt: boolean
+    lras_initiator: number | null  // port that quit out, if any
+    stage_id: number
+    timer_seconds: number
+    tournament_settings: boolean   // tournament stage, 8 minutes, 4 stocks
+    end_stocks: { port: number, stocks: number }[]
+  }
+}
+
+/** Every player's analysis and the game-level verdicts, from analyzeAll() */
+export type GameAnalysis = {
+  players: Record<number, PlayerAnalysis>  // by port, present players only
+  is_legal: boolean                        // every player passed
+  handwarmer: HandwarmerAnalysis
+  outcome: GameOutcome
+  teams: TeamSummary[]                     // empty without teams
 }
 
 export type GameSettings = {
@@ -156,6 +540,12 @@ export function getJoystickRegion(x: number, y: number): JoystickRegion {
   return get_joystick_region(x, y) as JoystickRegion
 }
 
+/** Name of an action state, or its hex ID if unknown. Character-specific states are only named for Fox and Falco (pass the game start character ID). */
+export function getActionStateName(state: number, characterId?: number): string {
+  ensureInitialized()
+  return get_action_state_name(state, characterId)
+}
+
 export function processAnalogStick(x: number, y: number, deadzone: boolean): Coord {
   ensureInitialized()
   return process_analog_stick(x, y, deadzone) as Coord
@@ -180,3 +570,9 @@ export function getTargetCoords(coords: Coord[]): Coord[] {
   ensureInitialized()
   return get_target_coords(coords) as Coord[]
 }
+
+/** Notches of a per-frame coordinate array; notch frames count from game frame -123 at index 0 */
+export function getCoordInventory(coords: Coord[]): CoordNotch[] {
+  ensureInitialized()
+  return get_coord_inventory(coords) as CoordNotch[]
+}